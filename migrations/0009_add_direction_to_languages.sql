@@ -0,0 +1,3 @@
+-- Migration number: 0009 	 2026-10-19T09:14:22.318Z
+ALTER TABLE Languages ADD COLUMN direction TEXT DEFAULT 'ltr' NOT NULL;
+UPDATE Languages SET direction = 'rtl' WHERE code IN ('ar', 'fa', 'he', 'ur');
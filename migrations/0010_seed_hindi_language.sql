@@ -0,0 +1,33 @@
+-- Migration number: 0010 	 2026-10-19T11:02:47.106Z
+INSERT INTO Languages (name, name_other, code, strip_diacritics, direction)
+SELECT 'Hindi', 'हिन्दी', 'hi', false, 'ltr'
+WHERE NOT EXISTS (SELECT 1 FROM Languages WHERE code = 'hi');
+
+-- Independent vowels and consonants are regular letters, matras and signs attach to them
+INSERT INTO Letters (letter, language_id, regular, hidden, name_en)
+SELECT v.column1, l.id, v.column2, false, v.column3
+FROM (VALUES
+  ('अ', true, 'a'), ('आ', true, 'aa'), ('इ', true, 'i'), ('ई', true, 'ii'),
+  ('उ', true, 'u'), ('ऊ', true, 'uu'), ('ऋ', true, 'ri'), ('ए', true, 'e'),
+  ('ऐ', true, 'ai'), ('ओ', true, 'o'), ('औ', true, 'au'),
+  ('क', true, 'ka'), ('ख', true, 'kha'), ('ग', true, 'ga'), ('घ', true, 'gha'), ('ङ', true, 'nga'),
+  ('च', true, 'ca'), ('छ', true, 'cha'), ('ज', true, 'ja'), ('झ', true, 'jha'), ('ञ', true, 'nya'),
+  ('ट', true, 'tta'), ('ठ', true, 'ttha'), ('ड', true, 'dda'), ('ढ', true, 'ddha'), ('ण', true, 'nna'),
+  ('त', true, 'ta'), ('थ', true, 'tha'), ('द', true, 'da'), ('ध', true, 'dha'), ('न', true, 'na'),
+  ('प', true, 'pa'), ('फ', true, 'pha'), ('ब', true, 'ba'), ('भ', true, 'bha'), ('म', true, 'ma'),
+  ('य', true, 'ya'), ('र', true, 'ra'), ('ल', true, 'la'), ('व', true, 'va'),
+  ('श', true, 'sha'), ('ष', true, 'ssa'), ('स', true, 'sa'), ('ह', true, 'ha'),
+  ('ा', false, 'aa matra'), ('ि', false, 'i matra'), ('ी', false, 'ii matra'),
+  ('ु', false, 'u matra'), ('ू', false, 'uu matra'), ('ृ', false, 'ri matra'),
+  ('े', false, 'e matra'), ('ै', false, 'ai matra'), ('ो', false, 'o matra'), ('ौ', false, 'au matra'),
+  ('ं', false, 'anusvara'), ('ः', false, 'visarga'), ('ँ', false, 'candrabindu'), ('्', false, 'virama')
+) AS v, Languages l
+WHERE l.code = 'hi';
+
+INSERT INTO Words (word, language_id)
+SELECT v.column1, l.id
+FROM (VALUES
+  ('घर'), ('कमल'), ('नमक'), ('आम'), ('फल'), ('सेब'), ('पानी'), ('किताब'),
+  ('बकरी'), ('मछली'), ('कुत्ता'), ('बिल्ली'), ('हाथी'), ('पत्ता'), ('चाँद')
+) AS v, Languages l
+WHERE l.code = 'hi';
@@ -0,0 +1,13 @@
+-- Migration number: 0011 	 2026-10-19T13:27:51.640Z
+ALTER TABLE Languages ADD COLUMN speech_locale TEXT;
+UPDATE Languages SET speech_locale = CASE code
+  WHEN 'en' THEN 'en-US'
+  WHEN 'fr' THEN 'fr-FR'
+  WHEN 'de' THEN 'de-DE'
+  WHEN 'es' THEN 'es-ES'
+  WHEN 'ru' THEN 'ru-RU'
+  WHEN 'ko' THEN 'ko-KR'
+  WHEN 'tr' THEN 'tr-TR'
+  WHEN 'ar' THEN 'ar-SA'
+  WHEN 'hi' THEN 'hi-IN'
+END;
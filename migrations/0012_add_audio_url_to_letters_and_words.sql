@@ -0,0 +1,3 @@
+-- Migration number: 0012 	 2026-10-19T14:48:09.225Z
+ALTER TABLE Letters ADD COLUMN audio_url TEXT;
+ALTER TABLE Words ADD COLUMN audio_url TEXT;
@@ -0,0 +1,3 @@
+-- Migration number: 0013 	 2026-10-19T15:36:44.871Z
+ALTER TABLE Letters ADD COLUMN spoken_name TEXT;
+ALTER TABLE Letters ADD COLUMN phoneme TEXT;
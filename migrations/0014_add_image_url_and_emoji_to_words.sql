@@ -0,0 +1,39 @@
+-- Migration number: 0014 	 2026-10-19T16:02:37.518Z
+ALTER TABLE Words ADD COLUMN image_url TEXT;
+ALTER TABLE Words ADD COLUMN emoji TEXT;
+
+UPDATE Words SET emoji = CASE word
+  WHEN 'घर' THEN '🏠'
+  WHEN 'कमल' THEN '🪷'
+  WHEN 'नमक' THEN '🧂'
+  WHEN 'आम' THEN '🥭'
+  WHEN 'सेब' THEN '🍎'
+  WHEN 'पानी' THEN '💧'
+  WHEN 'किताब' THEN '📖'
+  WHEN 'बकरी' THEN '🐐'
+  WHEN 'मछली' THEN '🐟'
+  WHEN 'कुत्ता' THEN '🐕'
+  WHEN 'बिल्ली' THEN '🐈'
+  WHEN 'हाथी' THEN '🐘'
+  WHEN 'पत्ता' THEN '🍃'
+  WHEN 'चाँद' THEN '🌙'
+END
+WHERE language_id = (SELECT id FROM Languages WHERE code = 'hi');
+
+UPDATE Words SET emoji = CASE lower(word)
+  WHEN 'cat' THEN '🐈'
+  WHEN 'dog' THEN '🐕'
+  WHEN 'fish' THEN '🐟'
+  WHEN 'bird' THEN '🐦'
+  WHEN 'apple' THEN '🍎'
+  WHEN 'sun' THEN '☀️'
+  WHEN 'moon' THEN '🌙'
+  WHEN 'house' THEN '🏠'
+  WHEN 'tree' THEN '🌳'
+  WHEN 'car' THEN '🚗'
+  WHEN 'book' THEN '📖'
+  WHEN 'ball' THEN '⚽'
+  WHEN 'water' THEN '💧'
+  WHEN 'star' THEN '⭐'
+END
+WHERE language_id = (SELECT id FROM Languages WHERE code = 'en');
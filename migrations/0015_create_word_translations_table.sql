@@ -0,0 +1,24 @@
+-- Migration number: 0015 	 2026-10-19T16:31:12.904Z
+PRAGMA defer_foreign_keys = on;
+CREATE TABLE IF NOT EXISTS WordTranslations (
+  id INTEGER PRIMARY KEY,
+  word_id INTEGER NOT NULL,
+  language_id INTEGER NOT NULL,
+  translation TEXT NOT NULL,
+  UNIQUE(word_id, language_id),
+  FOREIGN KEY(word_id) REFERENCES Words(id),
+  FOREIGN KEY(language_id) REFERENCES Languages(id)
+);
+PRAGMA defer_foreign_keys = off;
+
+-- English glosses for the Hindi starter words
+INSERT INTO WordTranslations (word_id, language_id, translation)
+SELECT w.id, en.id, v.column2
+FROM (VALUES
+  ('घर', 'house'), ('कमल', 'lotus'), ('नमक', 'salt'), ('आम', 'mango'), ('फल', 'fruit'),
+  ('सेब', 'apple'), ('पानी', 'water'), ('किताब', 'book'), ('बकरी', 'goat'), ('मछली', 'fish'),
+  ('कुत्ता', 'dog'), ('बिल्ली', 'cat'), ('हाथी', 'elephant'), ('पत्ता', 'leaf'), ('चाँद', 'moon')
+) AS v
+JOIN Words w ON w.word = v.column1
+JOIN Languages hi ON hi.id = w.language_id AND hi.code = 'hi'
+JOIN Languages en ON en.code = 'en';
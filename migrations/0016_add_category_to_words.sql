@@ -0,0 +1,2 @@
+-- Migration number: 0016 	 2026-10-19T17:10:44.381Z
+ALTER TABLE Words ADD COLUMN category TEXT;
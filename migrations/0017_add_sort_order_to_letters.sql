@@ -0,0 +1,2 @@
+-- Migration number: 0017 	 2026-10-19T17:52:06.117Z
+ALTER TABLE Letters ADD COLUMN sort_order INTEGER;
@@ -0,0 +1,25 @@
+-- Migration number: 0018 	 2026-10-19T18:34:51.662Z
+PRAGMA defer_foreign_keys = on;
+CREATE TABLE IF NOT EXISTS Users (
+  id INTEGER PRIMARY KEY,
+  email TEXT NOT NULL UNIQUE,
+  display_name TEXT,
+  created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- id is the SHA-256 of the session cookie, so a database leak does not expose live sessions
+CREATE TABLE IF NOT EXISTS Sessions (
+  id TEXT PRIMARY KEY,
+  user_id INTEGER NOT NULL,
+  expires_at TEXT NOT NULL,
+  FOREIGN KEY(user_id) REFERENCES Users(id)
+);
+
+-- One outstanding sign-in code per email address, stored hashed
+CREATE TABLE IF NOT EXISTS LoginCodes (
+  email TEXT PRIMARY KEY,
+  code_hash TEXT NOT NULL,
+  expires_at TEXT NOT NULL,
+  attempts INTEGER NOT NULL DEFAULT 0
+);
+PRAGMA defer_foreign_keys = off;
@@ -0,0 +1,12 @@
+-- Migration number: 0019 	 2026-10-19T19:05:12.408Z
+PRAGMA defer_foreign_keys = on;
+-- Learner profiles synced from a device; data is the profile as JSON, newest updated_at wins
+CREATE TABLE IF NOT EXISTS Profiles (
+  id TEXT NOT NULL,
+  user_id INTEGER NOT NULL,
+  data TEXT NOT NULL,
+  updated_at INTEGER NOT NULL,
+  PRIMARY KEY(user_id, id),
+  FOREIGN KEY(user_id) REFERENCES Users(id)
+);
+PRAGMA defer_foreign_keys = off;
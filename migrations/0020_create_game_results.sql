@@ -0,0 +1,20 @@
+-- Migration number: 0020 	 2026-10-19T19:41:37.215Z
+PRAGMA defer_foreign_keys = on;
+-- One row per word played; results belong to a signed-in user, a learner profile, or both
+CREATE TABLE IF NOT EXISTS GameResults (
+  id INTEGER PRIMARY KEY,
+  user_id INTEGER,
+  profile_id TEXT,
+  word_id INTEGER NOT NULL,
+  language_id INTEGER NOT NULL,
+  attempts INTEGER NOT NULL,
+  correct BOOLEAN NOT NULL,
+  score INTEGER NOT NULL,
+  created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  FOREIGN KEY(user_id) REFERENCES Users(id),
+  FOREIGN KEY(word_id) REFERENCES Words(id),
+  FOREIGN KEY(language_id) REFERENCES Languages(id)
+);
+CREATE INDEX IF NOT EXISTS idx_game_results_user ON GameResults(user_id);
+CREATE INDEX IF NOT EXISTS idx_game_results_profile ON GameResults(profile_id);
+PRAGMA defer_foreign_keys = off;
@@ -0,0 +1,18 @@
+-- Migration number: 0021 	 2026-10-19T20:14:08.551Z
+PRAGMA defer_foreign_keys = on;
+-- One row per letter of the target word each time an answer is checked
+CREATE TABLE IF NOT EXISTS LetterResults (
+  id INTEGER PRIMARY KEY,
+  user_id INTEGER,
+  profile_id TEXT,
+  letter_id INTEGER NOT NULL,
+  language_id INTEGER NOT NULL,
+  correct BOOLEAN NOT NULL,
+  created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  FOREIGN KEY(user_id) REFERENCES Users(id),
+  FOREIGN KEY(letter_id) REFERENCES Letters(id),
+  FOREIGN KEY(language_id) REFERENCES Languages(id)
+);
+CREATE INDEX IF NOT EXISTS idx_letter_results_user ON LetterResults(user_id, language_id);
+CREATE INDEX IF NOT EXISTS idx_letter_results_profile ON LetterResults(profile_id, language_id);
+PRAGMA defer_foreign_keys = off;
@@ -0,0 +1,21 @@
+-- Migration number: 0022 	 2026-10-19T20:52:44.730Z
+PRAGMA defer_foreign_keys = on;
+-- Prompt mode the word was played in, so leaderboard scores can be checked per mode
+ALTER TABLE GameResults ADD COLUMN mode TEXT NOT NULL DEFAULT 'word';
+
+-- Best score of each playing session; a session covers one language and mode
+CREATE TABLE IF NOT EXISTS Leaderboard (
+  id INTEGER PRIMARY KEY,
+  session_id TEXT NOT NULL UNIQUE,
+  user_id INTEGER,
+  profile_id TEXT,
+  name TEXT NOT NULL,
+  language_id INTEGER NOT NULL,
+  mode TEXT NOT NULL,
+  score INTEGER NOT NULL,
+  created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  FOREIGN KEY(user_id) REFERENCES Users(id),
+  FOREIGN KEY(language_id) REFERENCES Languages(id)
+);
+CREATE INDEX IF NOT EXISTS idx_leaderboard_language_mode ON Leaderboard(language_id, mode, created_at);
+PRAGMA defer_foreign_keys = off;
@@ -0,0 +1,28 @@
+-- Migration number: 0023 	 2026-10-19T21:37:12.418Z
+PRAGMA defer_foreign_keys = on;
+-- Server-side state of a game: the word being spelled and the points scored so far.
+-- A session covers one language and mode and doubles as the leaderboard session.
+CREATE TABLE IF NOT EXISTS GameSessions (
+  id TEXT PRIMARY KEY,
+  user_id INTEGER,
+  profile_id TEXT,
+  name TEXT NOT NULL,
+  language_id INTEGER NOT NULL,
+  mode TEXT NOT NULL,
+  word_id INTEGER NOT NULL,
+  -- Counts the words dealt so the client can tell a new word from a repeat
+  round INTEGER NOT NULL DEFAULT 1,
+  attempts INTEGER NOT NULL DEFAULT 0,
+  is_completed BOOLEAN NOT NULL DEFAULT 0,
+  awaiting_pronunciation BOOLEAN NOT NULL DEFAULT 0,
+  score INTEGER NOT NULL DEFAULT 0,
+  words_correct INTEGER NOT NULL DEFAULT 0,
+  -- GameResults row of the last finished word, for the pronunciation bonus
+  last_result_id INTEGER,
+  created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  FOREIGN KEY(user_id) REFERENCES Users(id),
+  FOREIGN KEY(language_id) REFERENCES Languages(id),
+  FOREIGN KEY(word_id) REFERENCES Words(id)
+);
+PRAGMA defer_foreign_keys = off;
@@ -0,0 +1,14 @@
+-- Migration number: 0024 	 2026-10-19T22:14:05.906Z
+PRAGMA defer_foreign_keys = on;
+-- Badges earned by each learner; the owner is the profile id, or 'user:<id>' for an account
+-- played without a profile
+CREATE TABLE IF NOT EXISTS Achievements (
+  owner TEXT NOT NULL,
+  user_id INTEGER,
+  profile_id TEXT,
+  achievement TEXT NOT NULL,
+  earned_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (owner, achievement),
+  FOREIGN KEY(user_id) REFERENCES Users(id)
+);
+PRAGMA defer_foreign_keys = off;
@@ -0,0 +1,23 @@
+-- Migration number: 0025 	 2026-10-19T22:48:31.072Z
+PRAGMA defer_foreign_keys = on;
+-- Minutes east of UTC where the game is played, so results count towards the learner's own day
+ALTER TABLE GameSessions ADD COLUMN utc_offset INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE GameResults ADD COLUMN local_date TEXT;
+UPDATE GameResults SET local_date = date(created_at) WHERE local_date IS NULL;
+CREATE INDEX IF NOT EXISTS idx_game_results_profile_date ON GameResults(profile_id, local_date);
+CREATE INDEX IF NOT EXISTS idx_game_results_user_date ON GameResults(user_id, local_date);
+
+-- Learners who want an email when they have not met their daily goal by the evening
+CREATE TABLE IF NOT EXISTS PracticeReminders (
+  owner TEXT PRIMARY KEY,
+  user_id INTEGER NOT NULL,
+  profile_id TEXT,
+  name TEXT NOT NULL,
+  daily_goal INTEGER NOT NULL,
+  utc_offset INTEGER NOT NULL,
+  -- Local date of the last reminder, so each day gets at most one
+  last_sent TEXT,
+  updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  FOREIGN KEY(user_id) REFERENCES Users(id)
+);
+PRAGMA defer_foreign_keys = off;
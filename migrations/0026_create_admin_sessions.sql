@@ -0,0 +1,9 @@
+-- Migration number: 0026 	 2026-10-19T23:41:07.518Z
+PRAGMA defer_foreign_keys = on;
+-- Signed-in admin sessions. id is the SHA-256 of the admin cookie, so neither the cookie nor
+-- the database holds the admin token itself
+CREATE TABLE IF NOT EXISTS AdminSessions (
+  id TEXT PRIMARY KEY,
+  expires_at TEXT NOT NULL
+);
+PRAGMA defer_foreign_keys = off;
@@ -0,0 +1,5 @@
+-- Migration number: 0027 	 2026-10-19T23:58:22.304Z
+PRAGMA defer_foreign_keys = on;
+-- Tiles the session's answers are made of, fixed when the session starts
+ALTER TABLE GameSessions ADD COLUMN tile_mode TEXT NOT NULL DEFAULT 'letters';
+PRAGMA defer_foreign_keys = off;
@@ -0,0 +1,7 @@
+-- Migration number: 0028 	 2026-10-20T00:21:46.815Z
+PRAGMA defer_foreign_keys = on;
+-- Game session the word was played in, so a session's leaderboard score can be added up from
+-- its recorded results
+ALTER TABLE GameResults ADD COLUMN session_id TEXT;
+CREATE INDEX IF NOT EXISTS idx_game_results_session ON GameResults(session_id);
+PRAGMA defer_foreign_keys = off;
@@ -0,0 +1,304 @@
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ssr")]
+use crate::database::Language;
+use crate::mastery::LetterMastery;
+#[cfg(feature = "ssr")]
+use crate::progress::Player;
+
+const FIRST_TRY_WORDS: u32 = 10;
+const STREAK_DAYS: u32 = 7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Achievement {
+    FirstWord,
+    FirstTryWords,
+    EveryLetter,
+    CompleteAlphabet,
+    WeekStreak,
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 5] = [
+        Achievement::FirstWord,
+        Achievement::FirstTryWords,
+        Achievement::EveryLetter,
+        Achievement::CompleteAlphabet,
+        Achievement::WeekStreak,
+    ];
+
+    // Stored in the Achievements table
+    pub fn code(&self) -> &'static str {
+        match self {
+            Achievement::FirstWord => "first_word",
+            Achievement::FirstTryWords => "first_try_words",
+            Achievement::CompleteAlphabet => "complete_alphabet",
+            Achievement::WeekStreak => "week_streak",
+            Achievement::EveryLetter => "every_letter",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.code() == code)
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Achievement::FirstWord => "🌱",
+            Achievement::FirstTryWords => "🎯",
+            Achievement::CompleteAlphabet => "🔤",
+            Achievement::WeekStreak => "🔥",
+            Achievement::EveryLetter => "🧩",
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Achievement::FirstWord => "First word",
+            Achievement::FirstTryWords => "Sharpshooter",
+            Achievement::CompleteAlphabet => "Alphabet master",
+            Achievement::WeekStreak => "Week streak",
+            Achievement::EveryLetter => "Every letter",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Achievement::FirstWord => "Spell your first word",
+            Achievement::FirstTryWords => "Spell 10 words on the first try",
+            Achievement::CompleteAlphabet => "Master every letter of an alphabet",
+            Achievement::WeekStreak => "Practise 7 days in a row",
+            Achievement::EveryLetter => "Play words with every letter of an alphabet",
+        }
+    }
+
+    pub fn is_earned(&self, stats: &AchievementStats) -> bool {
+        let alphabet_mastery = || {
+            stats.alphabet.iter().map(|letter_id| {
+                stats
+                    .mastery
+                    .iter()
+                    .find(|m| m.letter_id == *letter_id)
+                    .copied()
+                    .unwrap_or_default()
+            })
+        };
+        match self {
+            Achievement::FirstWord => stats.words_correct > 0,
+            Achievement::FirstTryWords => stats.first_try_words >= FIRST_TRY_WORDS,
+            Achievement::CompleteAlphabet => {
+                !stats.alphabet.is_empty() && alphabet_mastery().all(|m| m.is_mastered())
+            }
+            Achievement::WeekStreak => stats.best_day_run >= STREAK_DAYS,
+            Achievement::EveryLetter => {
+                !stats.alphabet.is_empty() && alphabet_mastery().all(|m| m.attempts() > 0)
+            }
+        }
+    }
+}
+
+// What the achievements are judged on; the letter counts are for the alphabet being played
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AchievementStats {
+    pub words_correct: u32,
+    pub first_try_words: u32,
+    // Most days practised in a row, in the learner's time zone
+    pub best_day_run: u32,
+    pub alphabet: Vec<u32>,
+    pub mastery: Vec<LetterMastery>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EarnedAchievement {
+    pub achievement: Achievement,
+    pub earned_at: String,
+}
+
+// Check the learner's results after an answer and save any achievements they have just earned,
+// which are returned so the game can celebrate them
+#[cfg(feature = "ssr")]
+pub async fn award(
+    conn: &sqlx_d1::D1Connection,
+    player: &Player,
+    language: &Language,
+) -> Result<Vec<Achievement>, ServerFnError> {
+    let Some(owner) = player.owner() else {
+        return Ok(vec![]);
+    };
+    let earned: Vec<Achievement> = sqlx_d1::query!(
+        "SELECT achievement FROM Achievements WHERE owner = ?",
+        owner
+    )
+    .fetch_all(conn)
+    .await
+    .map_err(|e| worker::Error::RustError(e.to_string()))?
+    .into_iter()
+    .filter_map(|row| Achievement::from_code(&row.achievement))
+    .collect();
+    if Achievement::ALL.iter().all(|a| earned.contains(a)) {
+        return Ok(vec![]);
+    }
+
+    let (words_correct, first_try_words) = match &player.profile_id {
+        Some(profile_id) => sqlx_d1::query!(
+            "SELECT COALESCE(SUM(correct), 0) AS words_correct, COALESCE(SUM(correct AND attempts = 1), 0) AS first_try_words FROM GameResults WHERE profile_id = ?",
+            profile_id
+        )
+        .fetch_one(conn)
+        .await
+        .map(|row| (row.words_correct, row.first_try_words)),
+        None => sqlx_d1::query!(
+            "SELECT COALESCE(SUM(correct), 0) AS words_correct, COALESCE(SUM(correct AND attempts = 1), 0) AS first_try_words FROM GameResults WHERE user_id = ?",
+            player.user_id
+        )
+        .fetch_one(conn)
+        .await
+        .map(|row| (row.words_correct, row.first_try_words)),
+    }
+    .map_err(|e| worker::Error::RustError(e.to_string()))?;
+    let days = crate::streak::practice_days(conn, player).await?;
+    let stats = AchievementStats {
+        words_correct: words_correct as u32,
+        first_try_words: first_try_words as u32,
+        best_day_run: crate::streak::best_day_run(&days),
+        // Hidden letters are left off the alphabet page, so a badge does not need them
+        alphabet: crate::database::get_letters_for_language(language.clone())
+            .await?
+            .into_iter()
+            .filter(|letter| letter.hidden != Some(true))
+            .map(|letter| letter.id)
+            .collect(),
+        mastery: crate::progress::get_letter_mastery(language.id, player.profile_id.clone())
+            .await?,
+    };
+
+    let mut new = vec![];
+    for achievement in Achievement::ALL {
+        if earned.contains(&achievement) || !achievement.is_earned(&stats) {
+            continue;
+        }
+        let code = achievement.code();
+        // Only a row that was actually inserted comes back, so nothing is celebrated twice
+        let inserted = sqlx_d1::query!(
+            "INSERT INTO Achievements (owner, user_id, profile_id, achievement) VALUES (?, ?, ?, ?) ON CONFLICT DO NOTHING RETURNING achievement",
+            owner,
+            player.user_id,
+            player.profile_id,
+            code
+        )
+        .fetch_optional(conn)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?;
+        if inserted.is_some() {
+            new.push(achievement);
+        }
+    }
+
+    Ok(new)
+}
+
+// Achievements of a learner profile, or of the signed-in account when no profile is given;
+// empty for a guest
+#[server]
+pub async fn get_achievements(
+    profile_id: Option<String>,
+) -> Result<Vec<EarnedAchievement>, ServerFnError> {
+    use axum::Extension;
+    use std::sync::Arc;
+    use worker::Env;
+
+    let player = Player {
+        user_id: crate::auth::get_current_user().await?.map(|user| user.id),
+        profile_id,
+    };
+    let Some(owner) = player.owner() else {
+        return Ok(vec![]);
+    };
+    let Extension::<Arc<Env>>(env) = leptos_axum::extract().await?;
+    let d1 = env.d1("alphabet_game_stg")?;
+    let conn = sqlx_d1::D1Connection::new(d1);
+
+    let achievements = sqlx_d1::query!(
+        "SELECT achievement, earned_at FROM Achievements WHERE owner = ? ORDER BY earned_at",
+        owner
+    )
+    .fetch_all(&conn)
+    .await
+    .map_err(|e| worker::Error::RustError(e.to_string()))?
+    .into_iter()
+    .filter_map(|row| {
+        Achievement::from_code(&row.achievement).map(|achievement| EarnedAchievement {
+            achievement,
+            earned_at: row.earned_at,
+        })
+    })
+    .collect();
+
+    Ok(achievements)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letter(letter_id: u32, correct: u32, incorrect: u32) -> LetterMastery {
+        LetterMastery {
+            letter_id,
+            correct,
+            incorrect,
+        }
+    }
+
+    #[test]
+    fn word_counts_reach_their_thresholds() {
+        let mut stats = AchievementStats::default();
+        assert!(!Achievement::FirstWord.is_earned(&stats));
+        stats.words_correct = 1;
+        assert!(Achievement::FirstWord.is_earned(&stats));
+
+        stats.first_try_words = FIRST_TRY_WORDS - 1;
+        assert!(!Achievement::FirstTryWords.is_earned(&stats));
+        stats.first_try_words = FIRST_TRY_WORDS;
+        assert!(Achievement::FirstTryWords.is_earned(&stats));
+    }
+
+    #[test]
+    fn week_streak_needs_seven_days() {
+        let stats = |best_day_run| AchievementStats {
+            best_day_run,
+            ..AchievementStats::default()
+        };
+        assert!(!Achievement::WeekStreak.is_earned(&stats(6)));
+        assert!(Achievement::WeekStreak.is_earned(&stats(7)));
+    }
+
+    #[test]
+    fn alphabet_badges_need_an_alphabet() {
+        let stats = AchievementStats {
+            mastery: vec![letter(1, 10, 0)],
+            ..AchievementStats::default()
+        };
+        assert!(!Achievement::EveryLetter.is_earned(&stats));
+        assert!(!Achievement::CompleteAlphabet.is_earned(&stats));
+    }
+
+    #[test]
+    fn alphabet_badges_count_letters_never_tried() {
+        let mut stats = AchievementStats {
+            alphabet: vec![1, 2],
+            mastery: vec![letter(1, 10, 0)],
+            ..AchievementStats::default()
+        };
+        // Letter 2 has no results at all
+        assert!(!Achievement::EveryLetter.is_earned(&stats));
+        assert!(!Achievement::CompleteAlphabet.is_earned(&stats));
+
+        stats.mastery.push(letter(2, 0, 1));
+        assert!(Achievement::EveryLetter.is_earned(&stats));
+        assert!(!Achievement::CompleteAlphabet.is_earned(&stats));
+
+        stats.mastery[1] = letter(2, 10, 0);
+        assert!(Achievement::CompleteAlphabet.is_earned(&stats));
+    }
+}
@@ -5,12 +5,22 @@ use leptos_router::{
     path,
 };
 
-use crate::components::game::AlphabetGame;
+use crate::components::{
+    account::AccountPage,
+    achievements::AchievementsPage,
+    admin::{AdminLettersPage, AdminPage, AdminWordsPage},
+    alphabet::AlphabetPage,
+    game::AlphabetGame,
+    leaderboard::LeaderboardPage,
+    progress::ProgressPage,
+};
 
 pub fn shell(options: LeptosOptions) -> impl IntoView {
     view! {
         <!DOCTYPE html>
-        <html lang="en">
+        // Defaults for pages without a language of their own; the game and alphabet pages set
+        // theirs with <Html>, whose attributes come first and so take precedence
+        <html lang="en" dir="ltr">
             <head>
                 <meta charset="utf-8" />
                 <meta name="viewport" content="width=device-width, initial-scale=1" />
@@ -40,6 +50,14 @@ pub fn App() -> impl IntoView {
             <main>
                 <Routes fallback=|| "Page not found.".into_view()>
                     <Route path=path!("/") view=HomePage />
+                    <Route path=path!("/alphabet") view=AlphabetPage />
+                    <Route path=path!("/account") view=AccountPage />
+                    <Route path=path!("/progress") view=ProgressPage />
+                    <Route path=path!("/leaderboard") view=LeaderboardPage />
+                    <Route path=path!("/achievements") view=AchievementsPage />
+                    <Route path=path!("/admin") view=AdminPage />
+                    <Route path=path!("/admin/letters") view=AdminLettersPage />
+                    <Route path=path!("/admin/words") view=AdminWordsPage />
                 </Routes>
             </main>
         </Router>
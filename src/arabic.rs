@@ -0,0 +1,232 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LetterForm {
+    Isolated,
+    Initial,
+    Medial,
+    Final,
+}
+
+impl LetterForm {
+    pub const ALL: [LetterForm; 4] = [
+        LetterForm::Isolated,
+        LetterForm::Initial,
+        LetterForm::Medial,
+        LetterForm::Final,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LetterForm::Isolated => "Isolated",
+            LetterForm::Initial => "Initial",
+            LetterForm::Medial => "Medial",
+            LetterForm::Final => "Final",
+        }
+    }
+}
+
+// (letter, isolated, final, initial, medial) from the Arabic Presentation Forms blocks.
+// Letters without initial/medial forms only join to the letter before them.
+type FormEntry = (char, char, Option<char>, Option<char>, Option<char>);
+
+#[rustfmt::skip]
+const PRESENTATION_FORMS: &[FormEntry] = &[
+    ('\u{0621}', '\u{FE80}', None, None, None),
+    ('\u{0622}', '\u{FE81}', Some('\u{FE82}'), None, None),
+    ('\u{0623}', '\u{FE83}', Some('\u{FE84}'), None, None),
+    ('\u{0624}', '\u{FE85}', Some('\u{FE86}'), None, None),
+    ('\u{0625}', '\u{FE87}', Some('\u{FE88}'), None, None),
+    ('\u{0626}', '\u{FE89}', Some('\u{FE8A}'), Some('\u{FE8B}'), Some('\u{FE8C}')),
+    ('\u{0627}', '\u{FE8D}', Some('\u{FE8E}'), None, None),
+    ('\u{0628}', '\u{FE8F}', Some('\u{FE90}'), Some('\u{FE91}'), Some('\u{FE92}')),
+    ('\u{0629}', '\u{FE93}', Some('\u{FE94}'), None, None),
+    ('\u{062A}', '\u{FE95}', Some('\u{FE96}'), Some('\u{FE97}'), Some('\u{FE98}')),
+    ('\u{062B}', '\u{FE99}', Some('\u{FE9A}'), Some('\u{FE9B}'), Some('\u{FE9C}')),
+    ('\u{062C}', '\u{FE9D}', Some('\u{FE9E}'), Some('\u{FE9F}'), Some('\u{FEA0}')),
+    ('\u{062D}', '\u{FEA1}', Some('\u{FEA2}'), Some('\u{FEA3}'), Some('\u{FEA4}')),
+    ('\u{062E}', '\u{FEA5}', Some('\u{FEA6}'), Some('\u{FEA7}'), Some('\u{FEA8}')),
+    ('\u{062F}', '\u{FEA9}', Some('\u{FEAA}'), None, None),
+    ('\u{0630}', '\u{FEAB}', Some('\u{FEAC}'), None, None),
+    ('\u{0631}', '\u{FEAD}', Some('\u{FEAE}'), None, None),
+    ('\u{0632}', '\u{FEAF}', Some('\u{FEB0}'), None, None),
+    ('\u{0633}', '\u{FEB1}', Some('\u{FEB2}'), Some('\u{FEB3}'), Some('\u{FEB4}')),
+    ('\u{0634}', '\u{FEB5}', Some('\u{FEB6}'), Some('\u{FEB7}'), Some('\u{FEB8}')),
+    ('\u{0635}', '\u{FEB9}', Some('\u{FEBA}'), Some('\u{FEBB}'), Some('\u{FEBC}')),
+    ('\u{0636}', '\u{FEBD}', Some('\u{FEBE}'), Some('\u{FEBF}'), Some('\u{FEC0}')),
+    ('\u{0637}', '\u{FEC1}', Some('\u{FEC2}'), Some('\u{FEC3}'), Some('\u{FEC4}')),
+    ('\u{0638}', '\u{FEC5}', Some('\u{FEC6}'), Some('\u{FEC7}'), Some('\u{FEC8}')),
+    ('\u{0639}', '\u{FEC9}', Some('\u{FECA}'), Some('\u{FECB}'), Some('\u{FECC}')),
+    ('\u{063A}', '\u{FECD}', Some('\u{FECE}'), Some('\u{FECF}'), Some('\u{FED0}')),
+    ('\u{0641}', '\u{FED1}', Some('\u{FED2}'), Some('\u{FED3}'), Some('\u{FED4}')),
+    ('\u{0642}', '\u{FED5}', Some('\u{FED6}'), Some('\u{FED7}'), Some('\u{FED8}')),
+    ('\u{0643}', '\u{FED9}', Some('\u{FEDA}'), Some('\u{FEDB}'), Some('\u{FEDC}')),
+    ('\u{0644}', '\u{FEDD}', Some('\u{FEDE}'), Some('\u{FEDF}'), Some('\u{FEE0}')),
+    ('\u{0645}', '\u{FEE1}', Some('\u{FEE2}'), Some('\u{FEE3}'), Some('\u{FEE4}')),
+    ('\u{0646}', '\u{FEE5}', Some('\u{FEE6}'), Some('\u{FEE7}'), Some('\u{FEE8}')),
+    ('\u{0647}', '\u{FEE9}', Some('\u{FEEA}'), Some('\u{FEEB}'), Some('\u{FEEC}')),
+    ('\u{0648}', '\u{FEED}', Some('\u{FEEE}'), None, None),
+    ('\u{0649}', '\u{FEEF}', Some('\u{FEF0}'), Some('\u{FBE8}'), Some('\u{FBE9}')),
+    ('\u{064A}', '\u{FEF1}', Some('\u{FEF2}'), Some('\u{FEF3}'), Some('\u{FEF4}')),
+];
+
+fn lookup(letter: char) -> Option<&'static FormEntry> {
+    PRESENTATION_FORMS.iter().find(|entry| entry.0 == letter)
+}
+
+// Harakat and other marks sit on top of a letter and do not affect joining
+fn is_transparent(c: char) -> bool {
+    matches!(c, '\u{0610}'..='\u{061A}' | '\u{064B}'..='\u{065F}' | '\u{0670}')
+}
+
+fn joins_to_previous(letter: char) -> bool {
+    lookup(letter).is_some_and(|entry| entry.2.is_some())
+}
+
+fn joins_to_next(letter: char) -> bool {
+    lookup(letter).is_some_and(|entry| entry.3.is_some())
+}
+
+// Remove harakat when the language is configured to strip diacritics
+pub fn post_process(word: &str, strip_diacritics: bool) -> String {
+    if strip_diacritics {
+        tashkil::remove(word).to_string()
+    } else {
+        word.to_string()
+    }
+}
+
+// Presentation form of a letter, falling back to the letter itself when the form does not exist
+pub fn presentation_form(letter: char, form: LetterForm) -> char {
+    let Some(&(_, isolated, final_form, initial, medial)) = lookup(letter) else {
+        return letter;
+    };
+    match form {
+        LetterForm::Isolated => isolated,
+        LetterForm::Final => final_form.unwrap_or(isolated),
+        LetterForm::Initial => initial.unwrap_or(isolated),
+        LetterForm::Medial => medial.or(final_form).unwrap_or(isolated),
+    }
+}
+
+// All four forms of a single letter for the alphabet page, None for non-Arabic letters
+pub fn all_forms(letter: &str) -> Option<[(LetterForm, char); 4]> {
+    let mut chars = letter.chars();
+    let (Some(c), None) = (chars.next(), chars.next()) else {
+        return None;
+    };
+    lookup(c)?;
+    Some(LetterForm::ALL.map(|form| (form, presentation_form(c, form))))
+}
+
+// Form each letter takes at its position in the word, skipping diacritics
+pub fn positional_forms(word: &str) -> Vec<(char, LetterForm)> {
+    let letters: Vec<char> = word.chars().filter(|c| !is_transparent(*c)).collect();
+    letters
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| lookup(**c).is_some())
+        .map(|(i, &c)| {
+            let joined_before = i > 0 && joins_to_next(letters[i - 1]) && joins_to_previous(c);
+            let joined_after =
+                i + 1 < letters.len() && joins_to_next(c) && joins_to_previous(letters[i + 1]);
+            let form = match (joined_before, joined_after) {
+                (false, false) => LetterForm::Isolated,
+                (false, true) => LetterForm::Initial,
+                (true, true) => LetterForm::Medial,
+                (true, false) => LetterForm::Final,
+            };
+            (c, form)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presentation_forms_of_a_joining_letter() {
+        // ب
+        let forms = LetterForm::ALL.map(|form| presentation_form('\u{0628}', form));
+        assert_eq!(forms, ['\u{FE8F}', '\u{FE91}', '\u{FE92}', '\u{FE90}']);
+    }
+
+    #[test]
+    fn non_joining_letters_fall_back_to_isolated_and_final() {
+        for letter in ['ا', 'د', 'ذ', 'ر', 'ز', 'و'] {
+            let isolated = presentation_form(letter, LetterForm::Isolated);
+            let final_form = presentation_form(letter, LetterForm::Final);
+            assert_ne!(isolated, final_form, "{letter}");
+            assert_eq!(
+                presentation_form(letter, LetterForm::Initial),
+                isolated,
+                "{letter}"
+            );
+            assert_eq!(
+                presentation_form(letter, LetterForm::Medial),
+                final_form,
+                "{letter}"
+            );
+        }
+    }
+
+    #[test]
+    fn letters_outside_the_table_are_unchanged() {
+        assert_eq!(presentation_form('a', LetterForm::Medial), 'a');
+        assert_eq!(all_forms("a"), None);
+        assert_eq!(all_forms("با"), None);
+    }
+
+    #[test]
+    fn positional_forms_join_letters() {
+        use LetterForm::*;
+        // سلسلة
+        assert_eq!(
+            positional_forms("سلسلة"),
+            vec![
+                ('س', Initial),
+                ('ل', Medial),
+                ('س', Medial),
+                ('ل', Medial),
+                ('ة', Final),
+            ]
+        );
+    }
+
+    #[test]
+    fn non_joining_letters_break_the_word() {
+        use LetterForm::*;
+        // The letter after ا د ذ ر ز و starts again as if at the beginning of the word
+        assert_eq!(
+            positional_forms("باب"),
+            vec![('ب', Initial), ('ا', Final), ('ب', Isolated)]
+        );
+        assert_eq!(
+            positional_forms("دار"),
+            vec![('د', Isolated), ('ا', Isolated), ('ر', Isolated)]
+        );
+        assert_eq!(
+            positional_forms("وزير"),
+            vec![
+                ('و', Isolated),
+                ('ز', Isolated),
+                ('ي', Initial),
+                ('ر', Final)
+            ]
+        );
+        assert_eq!(
+            positional_forms("ذهب"),
+            vec![('ذ', Isolated), ('ه', Initial), ('ب', Final)]
+        );
+    }
+
+    #[test]
+    fn harakat_do_not_affect_joining() {
+        use LetterForm::*;
+        assert_eq!(
+            positional_forms("بَابٌ"),
+            vec![('ب', Initial), ('ا', Final), ('ب', Isolated)]
+        );
+    }
+}
@@ -0,0 +1,470 @@
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ssr")]
+use std::{future::Future, pin::Pin, sync::Arc};
+
+#[cfg(feature = "ssr")]
+const SESSION_COOKIE: &str = "session";
+#[cfg(feature = "ssr")]
+const MAX_CODE_ATTEMPTS: i64 = 5;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx_d1::FromRow)]
+pub struct User {
+    pub id: u32,
+    pub email: String,
+    pub display_name: Option<String>,
+}
+
+// Signed-in user for the current request, added by `current_user_layer`
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, Default)]
+pub struct CurrentUser(pub Option<User>);
+
+#[cfg(feature = "ssr")]
+pub type SendResult<'a> = Pin<Box<dyn Future<Output = Result<(), String>> + 'a>>;
+
+// Delivers sign-in codes; swapped for `LogEmailSender` in local development and tests
+#[cfg(feature = "ssr")]
+pub trait EmailSender: Send + Sync {
+    fn send_login_code<'a>(&'a self, email: &'a str, code: &'a str) -> SendResult<'a>;
+}
+
+// Writes the code to the Worker log instead of sending mail
+#[cfg(feature = "ssr")]
+pub struct LogEmailSender;
+
+#[cfg(feature = "ssr")]
+impl EmailSender for LogEmailSender {
+    fn send_login_code<'a>(&'a self, email: &'a str, code: &'a str) -> SendResult<'a> {
+        Box::pin(async move {
+            leptos::logging::log!("Sign-in code for {email}: {code}");
+            Ok(())
+        })
+    }
+}
+
+// Posts a JSON message ({ from, to, subject, text }) to a transactional email API
+#[cfg(feature = "ssr")]
+pub struct HttpEmailSender {
+    pub endpoint: String,
+    pub api_key: String,
+    pub from: String,
+}
+
+#[cfg(feature = "ssr")]
+impl HttpEmailSender {
+    pub async fn send(&self, to: &str, subject: &str, text: &str) -> Result<(), String> {
+        let body = serde_json::json!({
+            "from": self.from,
+            "to": to,
+            "subject": subject,
+            "text": text,
+        });
+        let headers = worker::Headers::new();
+        headers
+            .set("Authorization", &format!("Bearer {}", self.api_key))
+            .and_then(|_| headers.set("Content-Type", "application/json"))
+            .map_err(|e| e.to_string())?;
+        let mut init = worker::RequestInit::new();
+        init.with_method(worker::Method::Post)
+            .with_headers(headers)
+            .with_body(Some(body.to_string().into()));
+        let request =
+            worker::Request::new_with_init(&self.endpoint, &init).map_err(|e| e.to_string())?;
+        let response = worker::Fetch::Request(request)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        match response.status_code() {
+            200..=299 => Ok(()),
+            status => Err(format!("Email API returned {status}")),
+        }
+    }
+
+    // Configured when EMAIL_API_URL, EMAIL_FROM and the EMAIL_API_KEY secret are set
+    pub fn from_env(env: &worker::Env) -> Option<Self> {
+        Some(Self {
+            endpoint: env.var("EMAIL_API_URL").ok()?.to_string(),
+            api_key: env.secret("EMAIL_API_KEY").ok()?.to_string(),
+            from: env.var("EMAIL_FROM").ok()?.to_string(),
+        })
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl EmailSender for HttpEmailSender {
+    fn send_login_code<'a>(&'a self, email: &'a str, code: &'a str) -> SendResult<'a> {
+        Box::pin(async move {
+            self.send(
+                email,
+                "Your Alphabet Game sign-in code",
+                &format!("Your sign-in code is {code}. It expires in 10 minutes."),
+            )
+            .await
+        })
+    }
+}
+
+// Used when the email API is not configured and logging was not asked for, so sign-in codes
+// and reminders fail to send rather than end up in the Worker log
+#[cfg(feature = "ssr")]
+pub struct NoEmailSender;
+
+#[cfg(feature = "ssr")]
+impl EmailSender for NoEmailSender {
+    fn send_login_code<'a>(&'a self, _email: &'a str, _code: &'a str) -> SendResult<'a> {
+        Box::pin(async { Err("Email is not configured".to_string()) })
+    }
+}
+
+// Local development sets EMAIL_LOG_ONLY=true in .dev.vars to log mail instead of sending it
+#[cfg(feature = "ssr")]
+pub fn log_email_only(env: &worker::Env) -> bool {
+    env.var("EMAIL_LOG_ONLY")
+        .is_ok_and(|value| value.to_string() == "true")
+}
+
+// Real email when the email API is configured, the log when asked for, otherwise nothing
+#[cfg(feature = "ssr")]
+pub fn email_sender(env: &worker::Env) -> Arc<dyn EmailSender> {
+    match HttpEmailSender::from_env(env) {
+        Some(sender) => Arc::new(sender),
+        None if log_email_only(env) => Arc::new(LogEmailSender),
+        None => Arc::new(NoEmailSender),
+    }
+}
+
+// Value of a request cookie
+#[cfg(feature = "ssr")]
+pub fn cookie(headers: &axum::http::HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get_all(axum::http::header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value.to_string())
+}
+
+#[cfg(feature = "ssr")]
+pub fn set_cookie(value: String) -> Result<(), ServerFnError> {
+    use axum::http::{HeaderValue, header::SET_COOKIE};
+
+    let header = HeaderValue::from_str(&value).map_err(ServerFnError::new)?;
+    expect_context::<leptos_axum::ResponseOptions>().append_header(SET_COOKIE, header);
+    Ok(())
+}
+
+#[cfg(feature = "ssr")]
+pub(crate) fn sha256(text: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    Sha256::digest(text.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[cfg(feature = "ssr")]
+pub(crate) fn random_bytes<const N: usize>() -> Result<[u8; N], ServerFnError> {
+    let mut bytes = [0u8; N];
+    getrandom::fill(&mut bytes).map_err(ServerFnError::new)?;
+    Ok(bytes)
+}
+
+// Random cookie value for a new session; only its SHA-256 is stored
+#[cfg(feature = "ssr")]
+pub(crate) fn session_token() -> Result<String, ServerFnError> {
+    Ok(random_bytes::<32>()?
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
+// Compares secrets without stopping at the first difference, so the time taken does not give
+// away how much of a guess was right. The lengths are not secret.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+#[cfg(feature = "ssr")]
+fn normalize_email(email: &str) -> Result<String, ServerFnError> {
+    let email = email.trim().to_lowercase();
+    match email.split_once('@') {
+        Some((user, domain)) if !user.is_empty() && domain.contains('.') => Ok(email),
+        _ => Err(ServerFnError::new("Enter a valid email address")),
+    }
+}
+
+// Six digits from a random number, keeping leading zeros
+#[cfg(feature = "ssr")]
+fn login_code(random: u32) -> String {
+    format!("{:06}", random % 1_000_000)
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CodeCheck {
+    Valid,
+    Wrong,
+    // Too many wrong guesses; a new code has to be requested
+    Locked,
+}
+
+// Check a typed code against the stored hash and the number of wrong guesses so far
+#[cfg(feature = "ssr")]
+fn check_login_code(code_hash: &str, attempts: i64, code: &str) -> CodeCheck {
+    if attempts >= MAX_CODE_ATTEMPTS {
+        CodeCheck::Locked
+    } else if constant_time_eq(code_hash.as_bytes(), sha256(code.trim()).as_bytes()) {
+        CodeCheck::Valid
+    } else {
+        CodeCheck::Wrong
+    }
+}
+
+#[cfg(feature = "ssr")]
+async fn session_user(env: &worker::Env, headers: &axum::http::HeaderMap) -> Option<User> {
+    let token = cookie(headers, SESSION_COOKIE)?;
+    let conn = sqlx_d1::D1Connection::new(env.d1("alphabet_game_stg").ok()?);
+    let session_id = sha256(&token);
+    sqlx_d1::query_as!(
+        User,
+        "SELECT u.id, u.email, u.display_name FROM Sessions s JOIN Users u ON u.id = s.user_id WHERE s.id = ? AND s.expires_at > datetime('now')",
+        session_id
+    )
+    .fetch_optional(&conn)
+    .await
+    .unwrap_or_else(|e| {
+        leptos::logging::error!("Error loading session: {e}");
+        None
+    })
+}
+
+// Looks up the session cookie once per request so server functions can read `CurrentUser`
+#[cfg(feature = "ssr")]
+#[worker::send]
+pub async fn current_user_layer(
+    mut request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    let env = request.extensions().get::<Arc<worker::Env>>().cloned();
+    let user = match env {
+        Some(env) => session_user(&env, request.headers()).await,
+        None => None,
+    };
+    request.extensions_mut().insert(CurrentUser(user));
+    next.run(request).await
+}
+
+// The signed-in user, or an error for server functions that need one
+#[cfg(feature = "ssr")]
+pub async fn require_user() -> Result<User, ServerFnError> {
+    use axum::Extension;
+
+    let Extension(CurrentUser(user)) = leptos_axum::extract().await?;
+    user.ok_or_else(|| ServerFnError::new("Sign in first"))
+}
+
+#[server]
+pub async fn get_current_user() -> Result<Option<User>, ServerFnError> {
+    use axum::Extension;
+
+    let Extension(CurrentUser(user)) = leptos_axum::extract().await?;
+    Ok(user)
+}
+
+// Email a six-digit code, replacing any earlier one, and return the normalized address
+#[server]
+pub async fn request_login_code(email: String) -> Result<String, ServerFnError> {
+    use axum::Extension;
+    use worker::Env;
+
+    let Extension::<Arc<Env>>(env) = leptos_axum::extract().await?;
+    // Neither the sender nor its future is Send, which is fine on the Worker's single thread
+    let Extension::<Arc<dyn EmailSender>>(sender) =
+        worker::send::SendFuture::new(leptos_axum::extract()).await?;
+    let email = normalize_email(&email)?;
+    let code = login_code(u32::from_le_bytes(random_bytes()?));
+    let code_hash = sha256(&code);
+    let d1 = env.d1("alphabet_game_stg")?;
+    let conn = sqlx_d1::D1Connection::new(d1);
+
+    sqlx_d1::query!(
+        "INSERT INTO LoginCodes (email, code_hash, expires_at, attempts) VALUES (?, ?, datetime('now', '+10 minutes'), 0) ON CONFLICT(email) DO UPDATE SET code_hash = excluded.code_hash, expires_at = excluded.expires_at, attempts = 0",
+        email,
+        code_hash
+    )
+    .execute(&conn)
+    .await
+    .map_err(|e| worker::Error::RustError(e.to_string()))?;
+
+    worker::send::SendFuture::new(sender.send_login_code(&email, &code))
+        .await
+        .map_err(ServerFnError::new)?;
+    Ok(email)
+}
+
+// Check the code, creating the account on first sign-in, and start a 30-day session
+#[server]
+pub async fn verify_login_code(email: String, code: String) -> Result<User, ServerFnError> {
+    use axum::Extension;
+    use worker::Env;
+
+    let Extension::<Arc<Env>>(env) = leptos_axum::extract().await?;
+    let email = normalize_email(&email)?;
+    let d1 = env.d1("alphabet_game_stg")?;
+    let conn = sqlx_d1::D1Connection::new(d1);
+    let invalid = || ServerFnError::new("That code is wrong or has expired");
+
+    let login = sqlx_d1::query!(
+        "SELECT code_hash, attempts FROM LoginCodes WHERE email = ? AND expires_at > datetime('now')",
+        email
+    )
+    .fetch_optional(&conn)
+    .await
+    .map_err(|e| worker::Error::RustError(e.to_string()))?
+    .ok_or_else(invalid)?;
+    match check_login_code(&login.code_hash, login.attempts, &code) {
+        CodeCheck::Valid => {}
+        CodeCheck::Wrong => {
+            sqlx_d1::query!(
+                "UPDATE LoginCodes SET attempts = attempts + 1 WHERE email = ?",
+                email
+            )
+            .execute(&conn)
+            .await
+            .map_err(|e| worker::Error::RustError(e.to_string()))?;
+            return Err(invalid());
+        }
+        CodeCheck::Locked => return Err(invalid()),
+    }
+
+    sqlx_d1::query!("DELETE FROM LoginCodes WHERE email = ?", email)
+        .execute(&conn)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?;
+    let user = sqlx_d1::query_as!(
+        User,
+        "INSERT INTO Users (email) VALUES (?) ON CONFLICT(email) DO UPDATE SET email = excluded.email RETURNING id, email, display_name",
+        email
+    )
+    .fetch_one(&conn)
+    .await
+    .map_err(|e| worker::Error::RustError(e.to_string()))?;
+
+    let token = session_token()?;
+    let session_id = sha256(&token);
+    sqlx_d1::query!(
+        "INSERT INTO Sessions (id, user_id, expires_at) VALUES (?, ?, datetime('now', '+30 days'))",
+        session_id,
+        user.id
+    )
+    .execute(&conn)
+    .await
+    .map_err(|e| worker::Error::RustError(e.to_string()))?;
+    set_cookie(format!(
+        "{SESSION_COOKIE}={token}; Path=/; HttpOnly; Secure; SameSite=Lax; Max-Age=2592000"
+    ))?;
+
+    Ok(user)
+}
+
+#[server]
+pub async fn sign_out() -> Result<(), ServerFnError> {
+    use axum::Extension;
+    use axum::http::HeaderMap;
+    use worker::Env;
+
+    let Extension::<Arc<Env>>(env) = leptos_axum::extract().await?;
+    let headers: HeaderMap = leptos_axum::extract().await?;
+    if let Some(token) = cookie(&headers, SESSION_COOKIE) {
+        let d1 = env.d1("alphabet_game_stg")?;
+        let conn = sqlx_d1::D1Connection::new(d1);
+        let session_id = sha256(&token);
+        sqlx_d1::query!("DELETE FROM Sessions WHERE id = ?", session_id)
+            .execute(&conn)
+            .await
+            .map_err(|e| worker::Error::RustError(e.to_string()))?;
+    }
+    set_cookie(format!(
+        "{SESSION_COOKIE}=; Path=/; HttpOnly; Secure; SameSite=Lax; Max-Age=0"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constant_time_eq_matches_only_equal_secrets() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret2"));
+        assert!(!constant_time_eq(b"", b"secret"));
+    }
+
+    // Runs a sender's future, which never waits on anything in these tests
+    #[cfg(feature = "ssr")]
+    fn block_on(future: SendResult<'_>) -> Result<(), String> {
+        let mut future = future;
+        let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+        match future.as_mut().poll(&mut context) {
+            std::task::Poll::Ready(result) => result,
+            std::task::Poll::Pending => panic!("sender future did not finish"),
+        }
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn login_code_keeps_six_digits() {
+        assert_eq!(login_code(42), "000042");
+        assert_eq!(login_code(1_234_567), "234567");
+        assert_eq!(login_code(u32::MAX).len(), 6);
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn issued_code_verifies_once_sent() {
+        let email = normalize_email("  Learner@Example.COM ").unwrap();
+        assert_eq!(email, "learner@example.com");
+        let code = login_code(123_456);
+        let code_hash = sha256(&code);
+        assert_eq!(
+            block_on(LogEmailSender.send_login_code(&email, &code)),
+            Ok(())
+        );
+
+        assert_eq!(check_login_code(&code_hash, 0, &code), CodeCheck::Valid);
+        assert_eq!(
+            check_login_code(&code_hash, 0, " 123456\n"),
+            CodeCheck::Valid
+        );
+        assert_eq!(check_login_code(&code_hash, 0, "123457"), CodeCheck::Wrong);
+        assert_eq!(
+            check_login_code(&code_hash, MAX_CODE_ATTEMPTS - 1, &code),
+            CodeCheck::Valid
+        );
+        // Even the right code is refused once the guesses are used up
+        assert_eq!(
+            check_login_code(&code_hash, MAX_CODE_ATTEMPTS, &code),
+            CodeCheck::Locked
+        );
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn codes_are_not_sent_without_email() {
+        assert!(block_on(NoEmailSender.send_login_code("learner@example.com", "123456")).is_err());
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn normalize_email_rejects_incomplete_addresses() {
+        assert!(normalize_email("learner").is_err());
+        assert!(normalize_email("@example.com").is_err());
+        assert!(normalize_email("learner@localhost").is_err());
+    }
+}
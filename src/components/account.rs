@@ -0,0 +1,103 @@
+use leptos::prelude::*;
+
+use crate::auth::{RequestLoginCode, SignOut, VerifyLoginCode, get_current_user};
+use crate::components::action_error::ActionError;
+use crate::components::styles::{BUTTON_CLASS, INPUT_CLASS};
+
+// Email a code, then exchange it for a session; signed-in users see who they are
+#[component]
+pub fn AccountPage() -> impl IntoView {
+    let request_code = ServerAction::<RequestLoginCode>::new();
+    let verify_code = ServerAction::<VerifyLoginCode>::new();
+    let sign_out = ServerAction::<SignOut>::new();
+    let user = Resource::new(
+        move || (verify_code.version().get(), sign_out.version().get()),
+        |_| get_current_user(),
+    );
+    // Address the code went to, once one has been sent
+    let code_sent_to = move || {
+        request_code
+            .value()
+            .get()
+            .and_then(|result| result.ok())
+            .unwrap_or_default()
+    };
+
+    view! {
+        <div class="h-full flex flex-col">
+            <header class="bg-teal-700 text-white p-4 flex items-center justify-between">
+                <a href="/" class="hover:bg-teal-600 p-2 rounded">
+                    <img src="/icons/backspace.svg" alt="Back" class="w-6 h-6 rtl:-scale-x-100" />
+                </a>
+                <h1 class="text-xl font-bold">"Account"</h1>
+                <div class="w-10"></div>
+            </header>
+            <div class="bg-yellow-200 flex-grow overflow-auto p-4 text-black">
+                <Suspense fallback=|| {
+                    view! { "Loading..." }
+                }>
+                    {move || match user.get() {
+                        Some(Ok(Some(user))) => {
+                            view! {
+                                <div class="flex flex-col space-y-2 max-w-md">
+                                    <p>"Signed in as " <b>{user.display_name.unwrap_or(user.email)}</b></p>
+                                    <button
+                                        on:click=move |_| {
+                                            sign_out.dispatch(SignOut {});
+                                        }
+                                        class=BUTTON_CLASS
+                                    >
+                                        "Sign out"
+                                    </button>
+                                </div>
+                            }
+                                .into_any()
+                        }
+                        Some(_) => {
+                            view! {
+                                <ActionForm action=request_code>
+                                    <div class="flex space-x-2 max-w-md">
+                                        <input
+                                            type="email"
+                                            name="email"
+                                            placeholder="Email address"
+                                            class=INPUT_CLASS
+                                        />
+                                        <button type="submit" class=BUTTON_CLASS>
+                                            "Send code"
+                                        </button>
+                                    </div>
+                                </ActionForm>
+                                <ActionError value=request_code.value() />
+                                <div class:hidden=move || code_sent_to().is_empty() class="mt-4">
+                                    <p class="mb-2">
+                                        "We sent a six-digit code to " {code_sent_to}
+                                    </p>
+                                    <ActionForm action=verify_code>
+                                        <div class="flex space-x-2 max-w-md">
+                                            <input type="hidden" name="email" prop:value=code_sent_to />
+                                            <input
+                                                type="text"
+                                                name="code"
+                                                inputmode="numeric"
+                                                autocomplete="one-time-code"
+                                                placeholder="123456"
+                                                class=INPUT_CLASS
+                                            />
+                                            <button type="submit" class=BUTTON_CLASS>
+                                                "Sign in"
+                                            </button>
+                                        </div>
+                                    </ActionForm>
+                                    <ActionError value=verify_code.value() />
+                                </div>
+                            }
+                                .into_any()
+                        }
+                        None => view! { "Loading..." }.into_any(),
+                    }}
+                </Suspense>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,133 @@
+use std::time::Duration;
+
+use leptos::prelude::*;
+use leptos_router::hooks::use_query;
+
+use crate::achievements::{Achievement, EarnedAchievement, get_achievements};
+use crate::components::progress::ProgressParams;
+use crate::game::GameContext;
+
+const TOAST_DURATION: Duration = Duration::from_secs(4);
+
+// Newly earned achievements pop up over the game one at a time
+#[component]
+pub fn AchievementToasts() -> impl IntoView {
+    let game_context = use_context::<GameContext>().expect("GameContext should be provided");
+    let toasts = game_context.new_achievements;
+    Effect::new(move || {
+        if toasts.with(|toasts| !toasts.is_empty()) {
+            set_timeout(
+                move || {
+                    toasts.update(|toasts| {
+                        if !toasts.is_empty() {
+                            toasts.remove(0);
+                        }
+                    })
+                },
+                TOAST_DURATION,
+            );
+        }
+    });
+
+    view! {
+        {move || {
+            toasts
+                .with(|toasts| toasts.first().copied())
+                .map(|achievement| {
+                    view! {
+                        <div class="fixed bottom-4 inset-x-0 flex justify-center z-20 pointer-events-none">
+                            <div class="bg-teal-800 text-white rounded shadow-lg px-4 py-2 flex items-center space-x-3">
+                                <span class="text-3xl">{achievement.icon()}</span>
+                                <div class="flex flex-col">
+                                    <span class="font-bold">{achievement.title()}</span>
+                                    <span class="text-sm text-teal-100">
+                                        {achievement.description()}
+                                    </span>
+                                </div>
+                            </div>
+                        </div>
+                    }
+                })
+        }}
+    }
+}
+
+// Badge gallery for the learner in `?profile=`, or for the signed-in account
+#[component]
+pub fn AchievementsPage() -> impl IntoView {
+    let query = use_query::<ProgressParams>();
+    let achievements_resource = Resource::new(
+        move || query.get().ok().and_then(|params| params.profile),
+        get_achievements,
+    );
+
+    view! {
+        <div class="h-full flex flex-col">
+            <header class="bg-teal-700 text-white p-4 flex items-center justify-between">
+                <a href="/" class="hover:bg-teal-600 p-2 rounded">
+                    <img src="/icons/backspace.svg" alt="Back" class="w-6 h-6 rtl:-scale-x-100" />
+                </a>
+                <h1 class="text-xl font-bold">"Achievements"</h1>
+                <div class="w-10"></div>
+            </header>
+            <div class="bg-yellow-200 flex-grow overflow-auto p-4 text-black">
+                <Suspense fallback=|| {
+                    view! { "Loading..." }
+                }>
+                    {move || {
+                        achievements_resource
+                            .get()
+                            .map(|result| {
+                                let earned = result
+                                    .inspect_err(|e| {
+                                        leptos::logging::log!("Error loading achievements: {:?}", e)
+                                    })
+                                    .unwrap_or_default();
+                                view! {
+                                    <div class="grid grid-cols-2 landscape:grid-cols-5 gap-4">
+                                        {Achievement::ALL
+                                            .into_iter()
+                                            .map(|achievement| {
+                                                let earned = earned
+                                                    .iter()
+                                                    .find(|e| e.achievement == achievement)
+                                                    .cloned();
+                                                view! { <Badge achievement earned /> }
+                                            })
+                                            .collect_view()}
+                                    </div>
+                                }
+                            })
+                    }}
+                </Suspense>
+            </div>
+        </div>
+    }
+}
+
+// Badges not earned yet are shown faded so learners can see what to aim for
+#[component]
+fn Badge(achievement: Achievement, earned: Option<EarnedAchievement>) -> impl IntoView {
+    let is_earned = earned.is_some();
+    // Only the date of the UTC timestamp is shown
+    let earned_on = earned.map(|e| {
+        e.earned_at
+            .split(' ')
+            .next()
+            .unwrap_or_default()
+            .to_string()
+    });
+
+    view! {
+        <div
+            class="bg-white/50 rounded p-4 flex flex-col items-center text-center space-y-1"
+            class:opacity-40=move || !is_earned
+            class:grayscale=move || !is_earned
+        >
+            <span class="text-5xl">{achievement.icon()}</span>
+            <h2 class="font-bold">{achievement.title()}</h2>
+            <p class="text-sm">{achievement.description()}</p>
+            {earned_on.map(|date| view! { <p class="text-xs text-teal-800">{date}</p> })}
+        </div>
+    }
+}
@@ -0,0 +1,17 @@
+use leptos::prelude::*;
+
+// Error message of a failed server action, shown under the form that dispatched it
+#[component]
+pub fn ActionError<T>(
+    #[prop(into)] value: Signal<Option<Result<T, ServerFnError>>>,
+) -> impl IntoView
+where
+    T: Send + Sync + 'static,
+{
+    move || {
+        value.with(|value| match value {
+            Some(Err(e)) => Some(view! { <p class="text-red-700 mt-2">{e.to_string()}</p> }),
+            _ => None,
+        })
+    }
+}
@@ -0,0 +1,587 @@
+use leptos::prelude::*;
+use leptos_router::hooks::use_query;
+
+use crate::components::{
+    action_error::ActionError,
+    alphabet::load_alphabet,
+    game::QueryParams,
+    styles::{BUTTON_CLASS, INPUT_CLASS},
+};
+use crate::database::{
+    AdminLogin, AdminLogout, DeleteLanguage, DeleteLetter, DeleteWord, Language, Letter,
+    SaveLanguage, SaveLetter, SaveWord, Word, get_languages, get_words_for_language, is_admin,
+};
+
+const DELETE_CLASS: &str = "bg-red-500 text-white px-2 py-1 rounded hover:bg-red-600";
+
+// Empty optional text fields are stored as NULL
+fn optional(text: String) -> Option<String> {
+    (!text.trim().is_empty()).then_some(text)
+}
+
+fn new_language() -> Language {
+    Language {
+        id: 0,
+        name: String::new(),
+        name_other: None,
+        code: String::new(),
+        strip_diacritics: false,
+        direction: "ltr".to_string(),
+        speech_locale: None,
+    }
+}
+
+async fn load_words(lang_code: Option<String>) -> Result<(Language, Vec<Word>), ServerFnError> {
+    let (language, _) = load_alphabet(lang_code).await?;
+    let words = get_words_for_language(language.clone()).await?;
+    Ok((language, words))
+}
+
+#[component]
+pub fn AdminPage() -> impl IntoView {
+    view! {
+        <AdminGate>
+            <LanguagesAdmin />
+        </AdminGate>
+    }
+}
+
+#[component]
+pub fn AdminLettersPage() -> impl IntoView {
+    view! {
+        <AdminGate>
+            <LettersAdmin />
+        </AdminGate>
+    }
+}
+
+#[component]
+pub fn AdminWordsPage() -> impl IntoView {
+    view! {
+        <AdminGate>
+            <WordsAdmin />
+        </AdminGate>
+    }
+}
+
+// Shows the sign-in form until the request carries a valid admin cookie
+#[component]
+fn AdminGate(children: ChildrenFn) -> impl IntoView {
+    let login = ServerAction::<AdminLogin>::new();
+    let logout = ServerAction::<AdminLogout>::new();
+    let is_admin = Resource::new(
+        move || (login.version().get(), logout.version().get()),
+        |_| is_admin(),
+    );
+
+    view! {
+        <div class="h-full flex flex-col">
+            <header class="bg-teal-700 text-white p-4 flex items-center justify-between">
+                <nav class="flex space-x-4">
+                    <a href="/" class="hover:underline">
+                        "Game"
+                    </a>
+                    <a href="/admin" class="hover:underline">
+                        "Languages"
+                    </a>
+                </nav>
+                <h1 class="text-xl font-bold">"Admin"</h1>
+                <button
+                    on:click=move |_| {
+                        logout.dispatch(AdminLogout {});
+                    }
+                    class="hover:underline"
+                >
+                    "Sign out"
+                </button>
+            </header>
+            <div class="bg-yellow-200 flex-grow overflow-auto p-4 text-black">
+                <Suspense fallback=|| {
+                    view! { "Loading..." }
+                }>
+                    {move || match is_admin.get() {
+                        Some(Ok(true)) => children().into_any(),
+                        Some(_) => {
+                            view! {
+                                <ActionForm action=login>
+                                    <div class="flex space-x-2 max-w-md">
+                                        <input
+                                            type="password"
+                                            name="token"
+                                            placeholder="Admin token"
+                                            class=INPUT_CLASS
+                                        />
+                                        <button type="submit" class=BUTTON_CLASS>
+                                            "Sign in"
+                                        </button>
+                                    </div>
+                                </ActionForm>
+                                <ActionError value=login.value() />
+                            }
+                                .into_any()
+                        }
+                        None => view! { "Loading..." }.into_any(),
+                    }}
+                </Suspense>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn LanguagesAdmin() -> impl IntoView {
+    let save = ServerAction::<SaveLanguage>::new();
+    let delete = ServerAction::<DeleteLanguage>::new();
+    let languages = Resource::new(
+        move || (save.version().get(), delete.version().get()),
+        |_| get_languages(),
+    );
+
+    view! {
+        <ActionError value=save.value() />
+        <ActionError value=delete.value() />
+        <table class="w-full">
+            <thead>
+                <tr class="text-start">
+                    <th>"Code"</th>
+                    <th>"Name"</th>
+                    <th>"Native name"</th>
+                    <th>"Direction"</th>
+                    <th>"Speech locale"</th>
+                    <th>"Strip diacritics"</th>
+                    <th></th>
+                </tr>
+            </thead>
+            <tbody>
+                {move || match languages.get() {
+                    Some(Ok(languages)) => {
+                        languages
+                            .into_iter()
+                            .chain(std::iter::once(new_language()))
+                            .map(|language| {
+                                view! { <LanguageRow language=language save=save delete=delete /> }
+                            })
+                            .collect_view()
+                            .into_any()
+                    }
+                    Some(Err(e)) => view! { <tr><td>{e.to_string()}</td></tr> }.into_any(),
+                    None => ().into_any(),
+                }}
+            </tbody>
+        </table>
+    }
+}
+
+#[component]
+fn LanguageRow(
+    language: Language,
+    save: ServerAction<SaveLanguage>,
+    delete: ServerAction<DeleteLanguage>,
+) -> impl IntoView {
+    let id = language.id;
+    let code = language.code.clone();
+    let draft = RwSignal::new(language);
+
+    view! {
+        <tr>
+            <td>
+                <input
+                    class=INPUT_CLASS
+                    prop:value=move || draft.with(|l| l.code.clone())
+                    on:input=move |ev| draft.update(|l| l.code = event_target_value(&ev))
+                />
+            </td>
+            <td>
+                <input
+                    class=INPUT_CLASS
+                    prop:value=move || draft.with(|l| l.name.clone())
+                    on:input=move |ev| draft.update(|l| l.name = event_target_value(&ev))
+                />
+            </td>
+            <td>
+                <input
+                    class=INPUT_CLASS
+                    prop:value=move || draft.with(|l| l.name_other.clone().unwrap_or_default())
+                    on:input=move |ev| {
+                        draft.update(|l| l.name_other = optional(event_target_value(&ev)))
+                    }
+                />
+            </td>
+            <td>
+                <select
+                    class=INPUT_CLASS
+                    prop:value=move || draft.with(|l| l.direction.clone())
+                    on:change=move |ev| draft.update(|l| l.direction = event_target_value(&ev))
+                >
+                    <option value="ltr">"ltr"</option>
+                    <option value="rtl">"rtl"</option>
+                </select>
+            </td>
+            <td>
+                <input
+                    class=INPUT_CLASS
+                    prop:value=move || draft.with(|l| l.speech_locale.clone().unwrap_or_default())
+                    on:input=move |ev| {
+                        draft.update(|l| l.speech_locale = optional(event_target_value(&ev)))
+                    }
+                />
+            </td>
+            <td>
+                <input
+                    type="checkbox"
+                    prop:checked=move || draft.with(|l| l.strip_diacritics)
+                    on:change=move |ev| {
+                        draft.update(|l| l.strip_diacritics = event_target_checked(&ev))
+                    }
+                />
+            </td>
+            <td class="flex space-x-2">
+                <button
+                    class=BUTTON_CLASS
+                    on:click=move |_| {
+                        save.dispatch(SaveLanguage {
+                            language: draft.get(),
+                        });
+                    }
+                >
+                    {if id == 0 { "Add" } else { "Save" }}
+                </button>
+                {(id != 0)
+                    .then(|| {
+                        view! {
+                            <a href=format!("/admin/letters?lang={code}") class=BUTTON_CLASS>
+                                "Letters"
+                            </a>
+                            <a href=format!("/admin/words?lang={code}") class=BUTTON_CLASS>
+                                "Words"
+                            </a>
+                            <button
+                                class=DELETE_CLASS
+                                on:click=move |_| {
+                                    delete.dispatch(DeleteLanguage { id });
+                                }
+                            >
+                                "Delete"
+                            </button>
+                        }
+                    })}
+            </td>
+        </tr>
+    }
+}
+
+#[component]
+fn LettersAdmin() -> impl IntoView {
+    let query = use_query::<QueryParams>();
+    let save = ServerAction::<SaveLetter>::new();
+    let delete = ServerAction::<DeleteLetter>::new();
+    let alphabet = Resource::new(
+        move || {
+            (
+                query.get().ok().and_then(|params| params.lang),
+                save.version().get(),
+                delete.version().get(),
+            )
+        },
+        |(lang_code, _, _)| load_alphabet(lang_code),
+    );
+
+    view! {
+        <ActionError value=save.value() />
+        <ActionError value=delete.value() />
+        {move || match alphabet.get() {
+            Some(Ok((language, letters))) => {
+                let blank = Letter {
+                    id: 0,
+                    letter: String::new(),
+                    language_id: language.id,
+                    regular: Some(true),
+                    hidden: Some(false),
+                    name_en: None,
+                    audio_url: None,
+                    spoken_name: None,
+                    phoneme: None,
+                    sort_order: None,
+                };
+                view! {
+                    <h2 class="text-lg font-bold mb-2">"Letters: " {language.name}</h2>
+                    <table class="w-full">
+                        <thead>
+                            <tr class="text-start">
+                                <th>"Order"</th>
+                                <th>"Letter"</th>
+                                <th>"English name"</th>
+                                <th>"Spoken name"</th>
+                                <th>"Phoneme"</th>
+                                <th>"Audio URL"</th>
+                                <th>"Regular"</th>
+                                <th>"Hidden"</th>
+                                <th></th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {letters
+                                .into_iter()
+                                .chain(std::iter::once(blank))
+                                .map(|letter| {
+                                    view! { <LetterRow letter=letter save=save delete=delete /> }
+                                })
+                                .collect_view()}
+                        </tbody>
+                    </table>
+                }
+                    .into_any()
+            }
+            Some(Err(e)) => view! { <p>{e.to_string()}</p> }.into_any(),
+            None => view! { "Loading..." }.into_any(),
+        }}
+    }
+}
+
+#[component]
+fn LetterRow(
+    letter: Letter,
+    save: ServerAction<SaveLetter>,
+    delete: ServerAction<DeleteLetter>,
+) -> impl IntoView {
+    let id = letter.id;
+    let draft = RwSignal::new(letter);
+
+    view! {
+        <tr>
+            <td>
+                <input
+                    type="number"
+                    min="0"
+                    class=INPUT_CLASS
+                    prop:value=move || {
+                        draft.with(|l| l.sort_order.map(|o| o.to_string()).unwrap_or_default())
+                    }
+                    on:input=move |ev| {
+                        draft.update(|l| l.sort_order = event_target_value(&ev).parse().ok())
+                    }
+                />
+            </td>
+            <td>
+                <input
+                    class=INPUT_CLASS
+                    prop:value=move || draft.with(|l| l.letter.clone())
+                    on:input=move |ev| draft.update(|l| l.letter = event_target_value(&ev))
+                />
+            </td>
+            <td>
+                <input
+                    class=INPUT_CLASS
+                    prop:value=move || draft.with(|l| l.name_en.clone().unwrap_or_default())
+                    on:input=move |ev| {
+                        draft.update(|l| l.name_en = optional(event_target_value(&ev)))
+                    }
+                />
+            </td>
+            <td>
+                <input
+                    class=INPUT_CLASS
+                    prop:value=move || draft.with(|l| l.spoken_name.clone().unwrap_or_default())
+                    on:input=move |ev| {
+                        draft.update(|l| l.spoken_name = optional(event_target_value(&ev)))
+                    }
+                />
+            </td>
+            <td>
+                <input
+                    class=INPUT_CLASS
+                    prop:value=move || draft.with(|l| l.phoneme.clone().unwrap_or_default())
+                    on:input=move |ev| {
+                        draft.update(|l| l.phoneme = optional(event_target_value(&ev)))
+                    }
+                />
+            </td>
+            <td>
+                <input
+                    class=INPUT_CLASS
+                    prop:value=move || draft.with(|l| l.audio_url.clone().unwrap_or_default())
+                    on:input=move |ev| {
+                        draft.update(|l| l.audio_url = optional(event_target_value(&ev)))
+                    }
+                />
+            </td>
+            <td>
+                <input
+                    type="checkbox"
+                    prop:checked=move || draft.with(|l| l.regular == Some(true))
+                    on:change=move |ev| {
+                        draft.update(|l| l.regular = Some(event_target_checked(&ev)))
+                    }
+                />
+            </td>
+            <td>
+                <input
+                    type="checkbox"
+                    prop:checked=move || draft.with(|l| l.hidden == Some(true))
+                    on:change=move |ev| {
+                        draft.update(|l| l.hidden = Some(event_target_checked(&ev)))
+                    }
+                />
+            </td>
+            <td class="flex space-x-2">
+                <button
+                    class=BUTTON_CLASS
+                    on:click=move |_| {
+                        save.dispatch(SaveLetter { letter: draft.get() });
+                    }
+                >
+                    {if id == 0 { "Add" } else { "Save" }}
+                </button>
+                {(id != 0)
+                    .then(|| {
+                        view! {
+                            <button
+                                class=DELETE_CLASS
+                                on:click=move |_| {
+                                    delete.dispatch(DeleteLetter { id });
+                                }
+                            >
+                                "Delete"
+                            </button>
+                        }
+                    })}
+            </td>
+        </tr>
+    }
+}
+
+#[component]
+fn WordsAdmin() -> impl IntoView {
+    let query = use_query::<QueryParams>();
+    let save = ServerAction::<SaveWord>::new();
+    let delete = ServerAction::<DeleteWord>::new();
+    let vocabulary = Resource::new(
+        move || {
+            (
+                query.get().ok().and_then(|params| params.lang),
+                save.version().get(),
+                delete.version().get(),
+            )
+        },
+        |(lang_code, _, _)| load_words(lang_code),
+    );
+
+    view! {
+        <ActionError value=save.value() />
+        <ActionError value=delete.value() />
+        {move || match vocabulary.get() {
+            Some(Ok((language, words))) => {
+                let blank = Word {
+                    language_id: language.id,
+                    ..Word::default()
+                };
+                view! {
+                    <h2 class="text-lg font-bold mb-2">"Words: " {language.name}</h2>
+                    <table class="w-full">
+                        <thead>
+                            <tr class="text-start">
+                                <th>"Word"</th>
+                                <th>"Category"</th>
+                                <th>"Emoji"</th>
+                                <th>"Image URL"</th>
+                                <th>"Audio URL"</th>
+                                <th></th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {words
+                                .into_iter()
+                                .chain(std::iter::once(blank))
+                                .map(|word| {
+                                    view! { <WordRow word=word save=save delete=delete /> }
+                                })
+                                .collect_view()}
+                        </tbody>
+                    </table>
+                }
+                    .into_any()
+            }
+            Some(Err(e)) => view! { <p>{e.to_string()}</p> }.into_any(),
+            None => view! { "Loading..." }.into_any(),
+        }}
+    }
+}
+
+#[component]
+fn WordRow(
+    word: Word,
+    save: ServerAction<SaveWord>,
+    delete: ServerAction<DeleteWord>,
+) -> impl IntoView {
+    let id = word.id;
+    let draft = RwSignal::new(word);
+
+    view! {
+        <tr>
+            <td>
+                <input
+                    class=INPUT_CLASS
+                    prop:value=move || draft.with(|w| w.word.clone())
+                    on:input=move |ev| draft.update(|w| w.word = event_target_value(&ev))
+                />
+            </td>
+            <td>
+                <input
+                    class=INPUT_CLASS
+                    prop:value=move || draft.with(|w| w.category.clone().unwrap_or_default())
+                    on:input=move |ev| {
+                        draft.update(|w| w.category = optional(event_target_value(&ev)))
+                    }
+                />
+            </td>
+            <td>
+                <input
+                    class=INPUT_CLASS
+                    prop:value=move || draft.with(|w| w.emoji.clone().unwrap_or_default())
+                    on:input=move |ev| draft.update(|w| w.emoji = optional(event_target_value(&ev)))
+                />
+            </td>
+            <td>
+                <input
+                    class=INPUT_CLASS
+                    prop:value=move || draft.with(|w| w.image_url.clone().unwrap_or_default())
+                    on:input=move |ev| {
+                        draft.update(|w| w.image_url = optional(event_target_value(&ev)))
+                    }
+                />
+            </td>
+            <td>
+                <input
+                    class=INPUT_CLASS
+                    prop:value=move || draft.with(|w| w.audio_url.clone().unwrap_or_default())
+                    on:input=move |ev| {
+                        draft.update(|w| w.audio_url = optional(event_target_value(&ev)))
+                    }
+                />
+            </td>
+            <td class="flex space-x-2">
+                <button
+                    class=BUTTON_CLASS
+                    on:click=move |_| {
+                        save.dispatch(SaveWord { word: draft.get() });
+                    }
+                >
+                    {if id == 0 { "Add" } else { "Save" }}
+                </button>
+                {(id != 0)
+                    .then(|| {
+                        view! {
+                            <button
+                                class=DELETE_CLASS
+                                on:click=move |_| {
+                                    delete.dispatch(DeleteWord { id });
+                                }
+                            >
+                                "Delete"
+                            </button>
+                        }
+                    })}
+            </td>
+        </tr>
+    }
+}
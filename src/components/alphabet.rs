@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+
+use leptos::prelude::*;
+use leptos_meta::Html;
+use leptos_router::hooks::use_query;
+
+use crate::components::game::QueryParams;
+use crate::database::{
+    Language, Letter, get_default_language, get_languages, get_letters_for_language,
+};
+use crate::mastery::{LetterMastery, MASTERED_SCORE};
+use crate::progress::get_letter_mastery;
+use crate::{arabic, indic};
+
+pub(crate) async fn load_alphabet(
+    lang_code: Option<String>,
+) -> Result<(Language, Vec<Letter>), ServerFnError> {
+    let languages = get_languages().await?;
+    let language = match lang_code.and_then(|code| languages.into_iter().find(|l| l.code == code)) {
+        Some(language) => language,
+        None => get_default_language().await?,
+    };
+    let letters = get_letters_for_language(language.clone()).await?;
+    Ok((language, letters))
+}
+
+// The alphabet with the learner's right and wrong counts for each letter
+async fn load_alphabet_mastery(
+    (lang_code, profile_id): (Option<String>, Option<String>),
+) -> Result<(Language, Vec<Letter>, HashMap<u32, LetterMastery>), ServerFnError> {
+    let (language, letters) = load_alphabet(lang_code).await?;
+    let mastery = get_letter_mastery(language.id, profile_id)
+        .await?
+        .into_iter()
+        .map(|m| (m.letter_id, m))
+        .collect();
+    Ok((language, letters, mastery))
+}
+
+#[component]
+pub fn AlphabetPage() -> impl IntoView {
+    let query = use_query::<QueryParams>();
+    let alphabet_resource = Resource::new(
+        move || {
+            let params = query.get().ok();
+            (
+                params.as_ref().and_then(|p| p.lang.clone()),
+                params.and_then(|p| p.profile),
+            )
+        },
+        load_alphabet_mastery,
+    );
+
+    view! {
+        <Suspense fallback=|| {
+            view! { "Loading..." }
+        }>
+            {move || {
+                match alphabet_resource.get() {
+                    Some(Ok((language, letters, mastery))) => {
+                        view! { <AlphabetContent language=language letters=letters mastery=mastery /> }
+                            .into_any()
+                    }
+                    Some(Err(e)) => {
+                        leptos::logging::log!("Error loading alphabet: {:?}", e);
+                        view! { "Error!" }.into_any()
+                    }
+                    None => view! { "Loading..." }.into_any(),
+                }
+            }}
+        </Suspense>
+    }
+}
+
+#[component]
+fn AlphabetContent(
+    language: Language,
+    letters: Vec<Letter>,
+    mastery: HashMap<u32, LetterMastery>,
+) -> impl IntoView {
+    let back_href = format!("/?lang={}", language.code);
+
+    view! {
+        <Html {..} lang=language.code.clone() dir=language.direction.clone() />
+        <div class="h-full flex flex-col">
+            <header class="bg-teal-700 text-white p-4 flex items-center justify-between">
+                <a href=back_href class="hover:bg-teal-600 p-2 rounded">
+                    <img src="/icons/backspace.svg" alt="Back" class="w-6 h-6 rtl:-scale-x-100" />
+                </a>
+                <h1 class="text-xl font-bold">
+                    {language.name_other.clone().unwrap_or(language.name.clone())}
+                </h1>
+                <div class="w-10"></div>
+            </header>
+            <div class="bg-yellow-200 flex-grow overflow-y-auto p-4">
+                <div class="grid grid-cols-3 landscape:grid-cols-6 gap-4">
+                    {letters
+                        .into_iter()
+                        .filter(|letter| letter.hidden != Some(true))
+                        .map(|letter| {
+                            let mastery = mastery.get(&letter.id).copied();
+                            view! { <AlphabetLetter letter=letter mastery=mastery /> }
+                        })
+                        .collect_view()}
+                </div>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn AlphabetLetter(letter: Letter, mastery: Option<LetterMastery>) -> impl IntoView {
+    let forms = arabic::all_forms(&letter.letter);
+    // Heatmap: red for letters often missed through to green for letters mastered
+    let background = match mastery.and_then(|m| m.score()) {
+        None => "bg-white/50",
+        Some(score) if score < 0.5 => "bg-red-300",
+        Some(score) if score < MASTERED_SCORE => "bg-orange-200",
+        Some(_) => "bg-green-300",
+    };
+    let counts = mastery
+        .filter(|m| m.attempts() > 0)
+        .map(|m| format!("✓{} ✗{}", m.correct, m.incorrect));
+
+    view! {
+        <div class=format!("{background} rounded p-2 flex flex-col items-center")>
+            <span class="text-black text-5xl font-bold">{indic::tile_label(&letter.letter)}</span>
+            {letter.name_en.map(|name| view! { <span class="text-sm text-gray-700">{name}</span> })}
+            {counts.map(|counts| view! { <span class="text-xs text-gray-700">{counts}</span> })}
+            {forms
+                .map(|forms| {
+                    view! {
+                        <div class="grid grid-cols-4 gap-1 mt-2 w-full">
+                            {forms
+                                .into_iter()
+                                .map(|(form, glyph)| {
+                                    view! {
+                                        <div class="flex flex-col items-center">
+                                            <span class="text-black text-2xl">{glyph.to_string()}</span>
+                                            <span class="text-[0.6rem] text-gray-700">{form.label()}</span>
+                                        </div>
+                                    }
+                                })
+                                .collect_view()}
+                        </div>
+                    }
+                })}
+        </div>
+    }
+}
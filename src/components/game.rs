@@ -1,21 +1,56 @@
+use std::time::Duration;
+
 use leptos::prelude::*;
+use leptos_meta::Html;
 use leptos_router::{hooks::use_query, params::Params};
 use serde::{Deserialize, Serialize};
 
-use crate::components::{header::GameHeader, letter_grid::LetterGrid};
+use crate::components::{
+    achievements::AchievementToasts, header::GameHeader, letter_grid::LetterGrid,
+    profiles::ProfilePicker, say_word::SayTheWord,
+};
 use crate::database::{
-    Language, get_default_language, get_languages, get_letters_for_language,
-    get_random_word_for_language,
+    Language, get_default_language, get_languages, get_letters_for_language, get_translation,
 };
-use crate::game::GameContext;
+use crate::game::{GameContext, PromptMode};
+use crate::profile::{Profile, ProfilesContext};
+use crate::recognition::RecognizerContext;
+use crate::session::{SessionMode, next_session_word, start_game_session};
+use crate::speech;
+use crate::streak::{self, save_practice_reminder};
+
+const REVEAL_DELAY: Duration = Duration::from_millis(1500);
 
 #[derive(Params, PartialEq, Clone, Serialize, Deserialize)]
-struct QueryParams {
-    lang: Option<String>,
+pub(crate) struct QueryParams {
+    pub(crate) lang: Option<String>,
+    pub(crate) profile: Option<String>,
 }
 
 #[component]
 pub fn AlphabetGame() -> impl IntoView {
+    let profiles = ProfilesContext::new();
+    provide_context(profiles);
+    // localStorage is only readable in the browser, so profiles load after hydration
+    Effect::new(move || profiles.load());
+
+    view! {
+        {move || {
+            if !profiles.loaded.get() {
+                view! { "Loading..." }.into_any()
+            } else if profiles.choosing.get() {
+                view! { <ProfilePicker /> }.into_any()
+            } else {
+                view! { <GameForProfile /> }.into_any()
+            }
+        }}
+    }
+}
+
+// Rebuilt whenever the learner changes so each one gets their own GameContext
+#[component]
+fn GameForProfile() -> impl IntoView {
+    let profiles = use_context::<ProfilesContext>().expect("ProfilesContext should be provided");
     let default_language_resource = OnceResource::new(get_default_language());
     let languages_resource = OnceResource::new(get_languages());
     let query = use_query::<QueryParams>();
@@ -24,27 +59,31 @@ pub fn AlphabetGame() -> impl IntoView {
             view! { "Loading..." }
         }>
             {move || {
+                let profile = profiles.active_profile();
+                // A language in the URL wins over the learner's chosen language
+                let lang_code = query
+                    .get()
+                    .ok()
+                    .and_then(|params| params.lang)
+                    .or_else(|| profile.as_ref().and_then(|p| p.language_code.clone()));
                 let start_language = match (
-                    query.get(),
+                    lang_code,
                     languages_resource.get(),
                     default_language_resource.get(),
                 ) {
-                    (Ok(query_params), _, Some(Ok(default_lang))) if query_params.lang.is_none() => {
-                        Some(default_lang)
-                    }
-                    (Ok(query_params), Some(Ok(languages)), Some(Ok(default_lang))) => {
-                        let found_lang = query_params
-                            .lang
-                            .and_then(|lang_code| {
-                                languages.iter().find(|l| l.code == *lang_code).cloned()
-                            })
+                    (None, _, Some(Ok(default_lang))) => Some(default_lang),
+                    (Some(lang_code), Some(Ok(languages)), Some(Ok(default_lang))) => {
+                        let found_lang = languages
+                            .iter()
+                            .find(|l| l.code == lang_code)
+                            .cloned()
                             .unwrap_or(default_lang);
                         Some(found_lang)
                     }
                     _ => None,
                 };
                 if let Some(lang) = start_language {
-                    view! { <GameContent default_language=lang /> }.into_any()
+                    view! { <GameContent default_language=lang profile=profile /> }.into_any()
                 } else {
                     view! { "Error!" }.into_any()
                 }
@@ -54,15 +93,54 @@ pub fn AlphabetGame() -> impl IntoView {
 }
 
 #[component]
-pub fn GameContent(default_language: Language) -> impl IntoView {
-    let game_context = GameContext::new(default_language);
+pub fn GameContent(default_language: Language, profile: Option<Profile>) -> impl IntoView {
+    let game_context = match &profile {
+        Some(profile) => GameContext::for_profile(default_language, profile),
+        None => GameContext::new(default_language),
+    };
     provide_context(game_context.clone());
+    // Keep a recognizer provided by a parent, such as a fake one in tests
+    if use_context::<RecognizerContext>().is_none() {
+        provide_context(RecognizerContext::browser());
+    }
 
+    // Carry on with the server's game while the language, prompt mode and tile mode stay the
+    // same, otherwise start a new one
     async fn next_word(ctx: &GameContext, lang: Language) {
-        match get_random_word_for_language(lang.clone()).await {
-            Ok(word) => {
-                ctx.reset_for_next_word(word);
+        let (weak_letters, mode, tile_mode) = ctx
+            .settings
+            .with_untracked(|s| (s.weak_letters, s.prompt, s.difficulty.tile_mode()));
+        let session = ctx.session.get_untracked().filter(|session| {
+            session.language_id == lang.id
+                && session.mode == mode
+                && session.tile_mode == tile_mode
+                && session.is_completed
+        });
+        let result = match session {
+            Some(session) => {
+                next_session_word(session.id, ctx.profile_id.clone(), weak_letters).await
             }
+            None => {
+                let previous = ctx
+                    .state
+                    .with_untracked(|s| (s.current_word.id != 0).then_some(s.current_word.id));
+                start_game_session(
+                    lang.id,
+                    ctx.profile_id.clone(),
+                    ctx.player_name.clone(),
+                    SessionMode {
+                        prompt: mode,
+                        tiles: tile_mode,
+                    },
+                    previous,
+                    weak_letters,
+                    streak::utc_offset(),
+                )
+                .await
+            }
+        };
+        match result {
+            Ok(session) => ctx.apply_session(session),
             Err(e) => {
                 leptos::logging::log!("Error loading word: {:?}", e);
             }
@@ -78,10 +156,8 @@ pub fn GameContent(default_language: Language) -> impl IntoView {
                 let current_language = game_context.get_language();
                 match get_letters_for_language(current_language.clone()).await {
                     Ok(letters_res) if !letters_res.is_empty() => {
-                        let alphabet_letters: Vec<String> =
-                            letters_res.into_iter().map(|l| l.letter).collect();
                         // set alphabet letters
-                        game_context.set_language_letters(alphabet_letters.clone());
+                        game_context.set_language_letters(letters_res);
                     }
                     Ok(_) => leptos::logging::log!(
                         "Returned empty vec of letters for {}",
@@ -109,10 +185,8 @@ pub fn GameContent(default_language: Language) -> impl IntoView {
                     game_context.set_language(&current_language.clone());
                     match get_letters_for_language(current_language.clone()).await {
                         Ok(letters_res) if !letters_res.is_empty() => {
-                            let alphabet_letters: Vec<String> =
-                                letters_res.into_iter().map(|l| l.letter).collect();
                             // set alphabet letters
-                            game_context.set_language_letters(alphabet_letters.clone());
+                            game_context.set_language_letters(letters_res);
                         }
                         Ok(_) => leptos::logging::log!(
                             "Returned empty vec of letters for {}",
@@ -129,24 +203,155 @@ pub fn GameContent(default_language: Language) -> impl IntoView {
     });
 
     // Handle word progression when current word is completed
+    let ready_for_next = Memo::new(move |_| {
+        game_context
+            .state
+            .with(|state| state.is_completed && !state.awaiting_pronunciation)
+    });
     Effect::new({
         let game_context = game_context.clone();
         move || {
-            let state = game_context.state.get();
-            if state.is_completed {
+            if ready_for_next.get() {
                 let game_context = game_context.clone();
-                // Wait a moment, then load next word
+                let language = game_context.state.with_untracked(|s| s.language.clone());
+                // A hidden word stays revealed for a moment before the next one loads
+                let delay = if game_context
+                    .settings
+                    .with_untracked(|s| s.prompt.shows_word())
+                {
+                    Duration::ZERO
+                } else {
+                    REVEAL_DELAY
+                };
+                set_timeout(
+                    move || {
+                        leptos::task::spawn_local(async move {
+                            next_word(&game_context, language).await;
+                        });
+                    },
+                    delay,
+                );
+            }
+        }
+    });
+
+    // In dictation mode each new word is read out as soon as it is dealt
+    let current_word = Memo::new(move |_| game_context.state.with(|s| s.current_word.word.clone()));
+    Effect::new({
+        let game_context = game_context.clone();
+        move || {
+            current_word.track();
+            if game_context
+                .settings
+                .with_untracked(|s| s.prompt == PromptMode::Dictation)
+            {
+                game_context.pronounce_current_word();
+            }
+        }
+    });
+
+    // Fetch the gloss whenever the word or translation language changes
+    let translation_request = Memo::new(move |_| {
+        let word_id = game_context.state.with(|s| s.current_word.id);
+        let language = game_context
+            .settings
+            .with(|s| s.translation_language().map(str::to_string));
+        (word_id, language)
+    });
+    Effect::new({
+        let translation = game_context.translation;
+        move || {
+            translation.set(None);
+            if let (word_id, Some(language_code)) = translation_request.get()
+                && word_id != 0
+            {
+                leptos::task::spawn_local(async move {
+                    match get_translation(word_id, language_code).await {
+                        Ok(gloss) => translation.set(gloss),
+                        Err(e) => leptos::logging::log!("Error loading translation: {:?}", e),
+                    }
+                });
+            }
+        }
+    });
+
+    // Save the learner's score, settings and language as they change
+    if let (Some(profile), Some(profiles)) = (profile, use_context::<ProfilesContext>()) {
+        let game_context = game_context.clone();
+        Effect::new(move || {
+            let (score, words_correct) = game_context.state.with(|s| (s.score, s.words_correct));
+            let settings = game_context.settings.get();
+            let language_code = game_context.current_language.with(|l| l.code.clone());
+            profiles.update(&profile.id, |p| {
+                p.score = score;
+                p.words_correct = words_correct;
+                p.settings = settings;
+                p.language_code = Some(language_code);
+            });
+        });
+    }
+
+    // Tell the server about the daily goal and reminders so it can remind a signed-in learner
+    let reminder_settings =
+        Memo::new(move |_| game_context.settings.with(|s| (s.daily_goal, s.reminders)));
+    Effect::new({
+        let game_context = game_context.clone();
+        move |previous: Option<(u32, bool)>| {
+            let (daily_goal, reminders) = reminder_settings.get();
+            // Nothing to turn off the first time round
+            if reminders || previous.is_some_and(|(_, was_on)| was_on) {
+                let profile_id = game_context.profile_id.clone();
+                let name = game_context.player_name.clone();
                 leptos::task::spawn_local(async move {
-                    next_word(&game_context, state.language).await;
+                    if let Err(e) = save_practice_reminder(
+                        profile_id,
+                        name,
+                        daily_goal,
+                        reminders,
+                        streak::utc_offset(),
+                    )
+                    .await
+                    {
+                        leptos::logging::log!("Error saving reminder: {:?}", e);
+                    }
                 });
             }
+            (daily_goal, reminders)
+        }
+    });
+
+    // Report when the browser has no voice for the current language
+    Effect::new({
+        let game_context = game_context.clone();
+        move || {
+            game_context.current_language.track();
+            game_context.refresh_speech_status();
         }
     });
 
+    // Voices load asynchronously, so check again once the browser has them
+    Effect::new({
+        let game_context = game_context.clone();
+        move || {
+            let game_context = game_context.clone();
+            speech::on_voices_changed(move || game_context.refresh_speech_status());
+        }
+    });
+
+    let current_language = game_context.current_language;
+
     view! {
+        // lang and dir follow the active language so RTL scripts lay out correctly
+        <Html
+            {..}
+            lang=move || current_language.get().code
+            dir=move || current_language.get().direction
+        />
         <div class="h-full flex flex-col">
             <GameHeader />
+            <SayTheWord />
             <LetterGrid />
+            <AchievementToasts />
         </div>
     }
 }
@@ -1,23 +1,116 @@
-use crate::components::language_selector::LanguageSelector;
-use crate::game::GameContext;
-use crate::speech::speak;
+use crate::components::{language_selector::LanguageSelector, settings_menu::SettingsMenu};
+use crate::database::Word;
+use crate::game::{GameContext, PromptMode};
+use crate::profile::ProfilesContext;
 use leptos::prelude::*;
 
+// Falls back to the written word when a picture or translation prompt has nothing to show
+fn word_prompt(
+    prompt: PromptMode,
+    revealed: bool,
+    word: Word,
+    translation: Option<String>,
+) -> AnyView {
+    if revealed {
+        return word.word.into_any();
+    }
+    match prompt {
+        PromptMode::Word => word.word.into_any(),
+        PromptMode::Dictation => "🔊".into_any(),
+        PromptMode::Picture => match (word.image_url, word.emoji) {
+            (Some(image_url), _) => view! {
+                <img src=image_url alt="Picture of the word" class="h-16 w-16 object-contain" />
+            }
+            .into_any(),
+            (None, Some(emoji)) => view! { <span class="text-4xl">{emoji}</span> }.into_any(),
+            (None, None) => word.word.into_any(),
+        },
+        PromptMode::Translate => translation.unwrap_or(word.word).into_any(),
+    }
+}
+
 #[component]
 pub fn GameHeader() -> impl IntoView {
     let game_context = use_context::<GameContext>().expect("GameContext should be provided");
     let state = game_context.state;
     let game_context_backspace = game_context.clone();
     let game_context_check = game_context.clone();
+    let current_language = game_context.current_language;
+    let speech_status = game_context.speech_status;
+    let settings = game_context.settings;
+    let translation = game_context.translation;
+    let streak = game_context.streak;
+    let profiles = use_context::<ProfilesContext>();
+    let profile_param = game_context
+        .profile_id
+        .as_ref()
+        .map(|id| format!("&profile={id}"))
+        .unwrap_or_default();
+    let profile_query = game_context
+        .profile_id
+        .as_ref()
+        .map(|id| format!("?profile={id}"))
+        .unwrap_or_default();
+    let progress_href = format!("/progress{profile_query}");
+    let achievements_href = format!("/achievements{profile_query}");
 
     view! {
         <header class="bg-teal-700 text-white p-4 flex items-center justify-between">
             // Left Section: Score, Help, Language
             <div class="flex items-start space-x-4">
-                <div class="flex space-x-2">
+                // Switch learner
+                {profiles
+                    .map(|profiles| {
+                        view! {
+                            <button
+                                on:click=move |_| profiles.choosing.set(true)
+                                class="text-2xl hover:bg-teal-600 px-1 rounded"
+                                title="Change learner"
+                            >
+                                {move || {
+                                    profiles
+                                        .active_profile()
+                                        .map(|p| p.avatar)
+                                        .unwrap_or_else(|| "🙂".to_string())
+                                }}
+                            </button>
+                        }
+                    })}
+                <a
+                    href=achievements_href
+                    class="flex space-x-2 hover:bg-teal-600 rounded"
+                    title="Achievements"
+                >
                     <img src="/icons/star.svg" alt="Score" class="object-fill" />
                     <span class="text-[2vw]">{move || state.get().score}</span>
+                </a>
+                // Days practised in a row, and today's words towards the daily goal
+                <div class="flex flex-col items-start text-sm" title="Daily streak">
+                    <span>{move || format!("🔥 {}", streak.get().days)}</span>
+                    {move || {
+                        let daily_goal = settings.get().daily_goal;
+                        let streak = streak.get();
+                        (daily_goal > 0)
+                            .then(|| {
+                                view! {
+                                    <span class:text-green-200=move || streak.goal_met(daily_goal)>
+                                        {format!("{}/{daily_goal} today", streak.words_today)}
+                                    </span>
+                                }
+                            })
+                    }}
                 </div>
+                {move || {
+                    speech_status
+                        .get()
+                        .map(|error| {
+                            view! {
+                                <span class="text-sm text-teal-200" title=error.to_string()>
+                                    "No voice"
+                                </span>
+                            }
+                        })
+                }}
             </div>
 
             // Center Section: Current Word and User Input
@@ -29,26 +122,31 @@ pub fn GameHeader() -> impl IntoView {
                         }
                         class="bg-red-500 text-white p-1 rounded hover:bg-red-600 active:bg-red-700 transition-colors"
                     >
-                        <img src="/icons/backspace.svg" alt="Backspace" class="w-6 h-6" />
+                        <img
+                            src="/icons/backspace.svg"
+                            alt="Backspace"
+                            class="w-6 h-6 rtl:-scale-x-100"
+                        />
                     </button>
                     <button
                         on:click=move |_| {
-                            let word = game_context.get_current_word();
-                            if !word.is_empty() {
-                                let lang = game_context_check.current_language.get();
-                                speak(&word, &lang.code);
-                            }
+                            game_context.pronounce_current_word();
                         }
                         class="bg-transparent border-none p-0 m-0 text-xl font-bold underline cursor-pointer"
+                        dir=move || current_language.get().direction
                     >
-                        {move || state.get().current_word.word}
+                        // Dictation, picture and translation prompts keep the word hidden until it has been checked
+                        {move || {
+                            let state = state.get();
+                            let prompt = settings.get().prompt;
+                            word_prompt(prompt, state.is_completed, state.current_word, translation.get())
+                        }}
                     </button>
                     <button
                         on:click=move |_| {
                             let word = state.get().user_input.word;
                             if !word.is_empty() {
-                                let lang = game_context_check.current_language.get();
-                                speak(&word, &lang.code);
+                                game_context_check.speak(&word);
                             }
                             game_context_check.check_spelling();
                         }
@@ -57,25 +155,48 @@ pub fn GameHeader() -> impl IntoView {
                         <img src="/icons/check.svg" alt="Check" class="w-6 h-6" />
                     </button>
                 </div>
-                <div class="text-lg mt-1 min-h-6">{move || state.get().user_input.word}</div>
+                {move || {
+                    let settings = settings.get();
+                    (settings.translation_language.is_some()
+                        && settings.prompt != PromptMode::Translate)
+                        .then(|| {
+                            view! { <div class="text-sm italic text-teal-100">{translation.get()}</div> }
+                        })
+                }}
+                <div class="text-lg mt-1 min-h-6" dir=move || current_language.get().direction>
+                    {move || state.get().user_input.word}
+                </div>
             </div>
 
             // Right Section: Menu, Control Buttons, and Alphabet
             <div class="flex items-start space-x-4">
                 <div class="flex flex-col items-end space-y-2">
                     <div class="flex space-x-2">
-                        <button class="text-xl hover:bg-teal-600 p-2 rounded">
-                            <img src="/icons/menu.svg" alt="Menu" class="w-6 h-6" />
-                        </button>
-                        <button class="text-xl hover:bg-teal-600 p-2 rounded">
+                        <SettingsMenu />
+                        <a
+                            href=progress_href
+                            class="text-xl hover:bg-teal-600 p-2 rounded"
+                            title="Progress"
+                        >
+                            "📈"
+                        </a>
+                        <a
+                            href=move || {
+                                format!("/alphabet?lang={}{profile_param}", current_language.get().code)
+                            }
+                            class="text-xl hover:bg-teal-600 p-2 rounded"
+                        >
                             <img src="/icons/alphabet.svg" alt="Alphabet" class="w-6 h-6" />
-                        </button>
+                        </a>
                     </div>
                     <div class="flex space-x-2">
                         <button class="text-xl hover:bg-teal-600 p-2 rounded">
                             <img src="/icons/help.svg" alt="Help" class="w-6 h-6" />
                         </button>
                         <LanguageSelector />
+                        <a href="/account" class="text-xl hover:bg-teal-600 p-2 rounded" title="Account">
+                            "👤"
+                        </a>
                     </div>
                 </div>
             </div>
@@ -0,0 +1,159 @@
+use leptos::prelude::*;
+use leptos_router::{hooks::use_query, params::Params};
+use serde::{Deserialize, Serialize};
+
+use crate::components::alphabet::load_alphabet;
+use crate::database::{Language, get_languages};
+use crate::game::PromptMode;
+use crate::leaderboard::{LeaderboardEntry, Period, get_leaderboard};
+
+const SELECT_CLASS: &str = "bg-teal-800 text-white px-2 py-1 rounded border border-teal-600";
+
+#[derive(Params, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct LeaderboardParams {
+    pub(crate) lang: Option<String>,
+    pub(crate) mode: Option<String>,
+}
+
+async fn load_leaderboard(
+    (lang_code, mode, period): (Option<String>, PromptMode, Period),
+) -> Result<(Language, Vec<LeaderboardEntry>), ServerFnError> {
+    let (language, _) = load_alphabet(lang_code).await?;
+    let entries = get_leaderboard(language.id, mode, period).await?;
+    Ok((language, entries))
+}
+
+#[component]
+pub fn LeaderboardPage() -> impl IntoView {
+    let query = use_query::<LeaderboardParams>();
+    let params = query.get_untracked().ok();
+    let lang_code = RwSignal::new(params.as_ref().and_then(|p| p.lang.clone()));
+    let mode = RwSignal::new(
+        params
+            .and_then(|p| p.mode)
+            .and_then(|code| PromptMode::from_code(&code))
+            .unwrap_or_default(),
+    );
+    let period = RwSignal::new(Period::default());
+    let languages_resource = OnceResource::new(get_languages());
+    let leaderboard_resource = Resource::new(
+        move || (lang_code.get(), mode.get(), period.get()),
+        load_leaderboard,
+    );
+    let back_href = move || match lang_code.get() {
+        Some(code) => format!("/?lang={code}"),
+        None => "/".to_string(),
+    };
+
+    view! {
+        <div class="h-full flex flex-col">
+            <header class="bg-teal-700 text-white p-4 flex flex-wrap items-center justify-between gap-2">
+                <a href=back_href class="hover:bg-teal-600 p-2 rounded">
+                    <img src="/icons/backspace.svg" alt="Back" class="w-6 h-6 rtl:-scale-x-100" />
+                </a>
+                <h1 class="text-xl font-bold">"Leaderboard"</h1>
+                <div class="flex flex-wrap gap-2">
+                    <Suspense fallback=|| ()>
+                        <select
+                            class=SELECT_CLASS
+                            prop:value=move || {
+                                lang_code
+                                    .get()
+                                    .or_else(|| {
+                                        leaderboard_resource
+                                            .get()
+                                            .and_then(Result::ok)
+                                            .map(|(language, _)| language.code)
+                                    })
+                                    .unwrap_or_default()
+                            }
+                            on:change=move |ev| lang_code.set(Some(event_target_value(&ev)))
+                        >
+                            {move || {
+                                languages_resource
+                                    .get()
+                                    .and_then(Result::ok)
+                                    .unwrap_or_default()
+                                    .into_iter()
+                                    .map(|language| {
+                                        view! {
+                                            <option value=language.code.clone()>
+                                                {language.name_other.unwrap_or(language.name)}
+                                            </option>
+                                        }
+                                    })
+                                    .collect_view()
+                            }}
+                        </select>
+                    </Suspense>
+                    <select
+                        class=SELECT_CLASS
+                        prop:value=move || mode.get().label()
+                        on:change=move |ev| {
+                            if let Some(value) = PromptMode::from_label(&event_target_value(&ev)) {
+                                mode.set(value);
+                            }
+                        }
+                    >
+                        {PromptMode::ALL
+                            .into_iter()
+                            .map(|prompt| {
+                                view! { <option value=prompt.label()>{prompt.label()}</option> }
+                            })
+                            .collect_view()}
+                    </select>
+                    <select
+                        class=SELECT_CLASS
+                        prop:value=move || period.get().label()
+                        on:change=move |ev| {
+                            if let Some(value) = Period::from_label(&event_target_value(&ev)) {
+                                period.set(value);
+                            }
+                        }
+                    >
+                        {Period::ALL
+                            .into_iter()
+                            .map(|period| {
+                                view! { <option value=period.label()>{period.label()}</option> }
+                            })
+                            .collect_view()}
+                    </select>
+                </div>
+            </header>
+            <div class="bg-yellow-200 flex-grow overflow-auto p-4 text-black">
+                <Suspense fallback=|| {
+                    view! { "Loading..." }
+                }>
+                    {move || match leaderboard_resource.get() {
+                        Some(Ok((_, entries))) if entries.is_empty() => {
+                            view! { <p>"No scores yet. Be the first!"</p> }.into_any()
+                        }
+                        Some(Ok((_, entries))) => {
+                            view! {
+                                <ol class="max-w-md flex flex-col space-y-1">
+                                    {entries
+                                        .into_iter()
+                                        .map(|entry| {
+                                            view! {
+                                                <li class="bg-white/50 rounded px-3 py-2 flex justify-between">
+                                                    <span>{format!("{}. {}", entry.rank, entry.name)}</span>
+                                                    <span class="font-bold">{entry.score}</span>
+                                                </li>
+                                            }
+                                        })
+                                        .collect_view()}
+                                </ol>
+                            }
+                                .into_any()
+                        }
+                        Some(Err(e)) => {
+                            leptos::logging::log!("Error loading leaderboard: {:?}", e);
+                            view! { "Error!" }.into_any()
+                        }
+                        None => view! { "Loading..." }.into_any(),
+                    }}
+                </Suspense>
+            </div>
+        </div>
+    }
+}
@@ -6,6 +6,7 @@ use leptos::prelude::*;
 pub fn LetterGrid() -> impl IntoView {
     let game_context = use_context::<GameContext>().expect("GameContext should be provided");
     let state = game_context.state;
+    let settings = game_context.settings;
 
     view! {
         <div class="bg-yellow-200 flex-grow">
@@ -14,14 +15,16 @@ pub fn LetterGrid() -> impl IntoView {
                     view! { <p class="text-white text-center">"Loading..."</p> }
                 }>
                     {move || {
-                        let game_letters = state.with(|s| s.game_letters.clone());
+                        let grid_labels = settings.with(|settings| {
+                            state.with(|s| s.grid_labels(settings))
+                        });
 
                         view! {
                             <div class="grid grid-cols-3 landscape:grid-cols-4 h-full">
-                                {game_letters
+                                {grid_labels
                                     .into_iter()
-                                    .map(|letter| {
-                                        view! { <LetterButton letter=letter /> }
+                                    .map(|(letter, label)| {
+                                        view! { <LetterButton letter=letter label=label /> }
                                     })
                                     .collect_view()}
                             </div>
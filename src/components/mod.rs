@@ -1,5 +1,16 @@
+pub mod achievements;
+pub mod account;
+pub mod action_error;
+pub mod admin;
+pub mod alphabet;
 pub mod game;
 pub mod header;
 pub mod language_selector;
+pub mod leaderboard;
 pub mod letter_button;
 pub mod letter_grid;
+pub mod profiles;
+pub mod progress;
+pub mod say_word;
+pub mod settings_menu;
+pub mod styles;
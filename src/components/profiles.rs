@@ -0,0 +1,198 @@
+use leptos::prelude::*;
+
+use crate::auth::get_current_user;
+use crate::components::styles::{BUTTON_CLASS, INPUT_CLASS};
+use crate::database::get_languages;
+use crate::game::Difficulty;
+use crate::profile::{
+    AVATARS, Profile, ProfilesContext, delete_synced_profile, merge_profiles, sync_profiles,
+};
+
+// Who is playing? Shown at startup on devices with saved learners
+#[component]
+pub fn ProfilePicker() -> impl IntoView {
+    let profiles = use_context::<ProfilesContext>().expect("ProfilesContext should be provided");
+    let user = OnceResource::new(get_current_user());
+
+    // Signed-in devices share their learners with the account
+    Effect::new(move || {
+        if let Some(Ok(Some(_))) = user.get() {
+            let local = profiles.profiles.get_untracked();
+            leptos::task::spawn_local(async move {
+                match sync_profiles(local.clone()).await {
+                    Ok(remote) => profiles.replace(merge_profiles(local, remote)),
+                    Err(e) => leptos::logging::log!("Error syncing profiles: {:?}", e),
+                }
+            });
+        }
+    });
+
+    let remove = move |id: String| {
+        profiles.remove(&id);
+        if let Some(Ok(Some(_))) = user.get_untracked() {
+            leptos::task::spawn_local(async move {
+                if let Err(e) = delete_synced_profile(id).await {
+                    leptos::logging::log!("Error deleting synced profile: {:?}", e);
+                }
+            });
+        }
+    };
+
+    view! {
+        <div class="h-full flex flex-col">
+            <header class="bg-teal-700 text-white p-4 flex items-center justify-between">
+                <div class="w-10"></div>
+                <h1 class="text-xl font-bold">"Who is playing?"</h1>
+                <a href="/account" class="text-xl hover:bg-teal-600 p-2 rounded" title="Account">
+                    "👤"
+                </a>
+            </header>
+            <div class="bg-yellow-200 flex-grow overflow-auto p-4 text-black flex flex-col space-y-6">
+                <div class="grid grid-cols-3 landscape:grid-cols-6 gap-4">
+                    <For
+                        each=move || profiles.profiles.get()
+                        key=|profile| (profile.id.clone(), profile.name.clone(), profile.avatar.clone())
+                        let:profile
+                    >
+                        <ProfileCard profile=profile on_remove=remove />
+                    </For>
+                    <button
+                        on:click=move |_| profiles.select(None)
+                        class="bg-white/50 rounded p-2 flex flex-col items-center hover:bg-white/80"
+                    >
+                        <span class="text-5xl">"🙂"</span>
+                        <span class="text-sm">"Guest"</span>
+                    </button>
+                </div>
+                <NewProfileForm />
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn ProfileCard(
+    profile: Profile,
+    on_remove: impl Fn(String) + Copy + Send + 'static,
+) -> impl IntoView {
+    let profiles = use_context::<ProfilesContext>().expect("ProfilesContext should be provided");
+    let id = profile.id.clone();
+    let remove_id = profile.id.clone();
+
+    view! {
+        <div class="bg-white/50 rounded p-2 flex flex-col items-center relative">
+            <button
+                on:click=move |_| profiles.select(Some(id.clone()))
+                class="flex flex-col items-center w-full hover:bg-white/50 rounded"
+            >
+                <span class="text-5xl">{profile.avatar}</span>
+                <span class="text-sm font-bold">{profile.name}</span>
+                <span class="text-xs text-gray-700">{format!("⭐ {}", profile.score)}</span>
+            </button>
+            <button
+                on:click=move |_| on_remove(remove_id.clone())
+                class="absolute top-0 end-1 text-gray-600 hover:text-red-700"
+                title="Remove"
+            >
+                "×"
+            </button>
+        </div>
+    }
+}
+
+#[component]
+fn NewProfileForm() -> impl IntoView {
+    let profiles = use_context::<ProfilesContext>().expect("ProfilesContext should be provided");
+    let languages_resource = OnceResource::new(get_languages());
+    let name = RwSignal::new(String::new());
+    let avatar = RwSignal::new(AVATARS[0].to_string());
+    let language_code = RwSignal::new(String::new());
+    let difficulty = RwSignal::new(Difficulty::default());
+
+    let add = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        let name_value = name.get_untracked().trim().to_string();
+        if name_value.is_empty() {
+            return;
+        }
+        let mut profile = Profile::new(name_value, avatar.get_untracked());
+        let code = language_code.get_untracked();
+        profile.language_code = (!code.is_empty()).then_some(code);
+        profile.settings.difficulty = difficulty.get_untracked();
+        profiles.add(profile);
+        name.set(String::new());
+    };
+
+    view! {
+        <form on:submit=add class="flex flex-col space-y-2 max-w-md">
+            <h2 class="font-bold">"Add a learner"</h2>
+            <input
+                type="text"
+                placeholder="Name"
+                class=INPUT_CLASS
+                prop:value=move || name.get()
+                on:input=move |ev| name.set(event_target_value(&ev))
+            />
+            <div class="flex flex-wrap gap-1">
+                {AVATARS
+                    .into_iter()
+                    .map(|choice| {
+                        view! {
+                            <button
+                                type="button"
+                                on:click=move |_| avatar.set(choice.to_string())
+                                class="text-3xl p-1 rounded"
+                                class:bg-teal-300=move || avatar.get() == choice
+                            >
+                                {choice}
+                            </button>
+                        }
+                    })
+                    .collect_view()}
+            </div>
+            <Suspense fallback=|| ()>
+                <select
+                    class=INPUT_CLASS
+                    prop:value=move || language_code.get()
+                    on:change=move |ev| language_code.set(event_target_value(&ev))
+                >
+                    <option value="">"Default language"</option>
+                    {move || {
+                        languages_resource
+                            .get()
+                            .and_then(Result::ok)
+                            .unwrap_or_default()
+                            .into_iter()
+                            .map(|language| {
+                                view! {
+                                    <option value=language.code.clone()>
+                                        {language.name_other.unwrap_or(language.name)}
+                                    </option>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </select>
+            </Suspense>
+            <select
+                class=INPUT_CLASS
+                prop:value=move || difficulty.get().label()
+                on:change=move |ev| {
+                    if let Some(value) = Difficulty::from_label(&event_target_value(&ev)) {
+                        difficulty.set(value);
+                    }
+                }
+            >
+                {Difficulty::ALL
+                    .into_iter()
+                    .map(|difficulty| {
+                        view! { <option value=difficulty.label()>{difficulty.label()}</option> }
+                    })
+                    .collect_view()}
+            </select>
+            <button type="submit" class=BUTTON_CLASS>
+                "Add"
+            </button>
+        </form>
+    }
+}
@@ -0,0 +1,98 @@
+use leptos::prelude::*;
+use leptos_router::{hooks::use_query, params::Params};
+use serde::{Deserialize, Serialize};
+
+use crate::progress::{LanguageProgress, get_progress};
+
+#[derive(Params, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct ProgressParams {
+    pub(crate) profile: Option<String>,
+}
+
+// Totals for the learner in `?profile=`, or for the signed-in account
+#[component]
+pub fn ProgressPage() -> impl IntoView {
+    let query = use_query::<ProgressParams>();
+    let progress_resource = Resource::new(
+        move || query.get().ok().and_then(|params| params.profile),
+        get_progress,
+    );
+
+    view! {
+        <div class="h-full flex flex-col">
+            <header class="bg-teal-700 text-white p-4 flex items-center justify-between">
+                <a href="/" class="hover:bg-teal-600 p-2 rounded">
+                    <img src="/icons/backspace.svg" alt="Back" class="w-6 h-6 rtl:-scale-x-100" />
+                </a>
+                <h1 class="text-xl font-bold">"Progress"</h1>
+                <div class="w-10"></div>
+            </header>
+            <div class="bg-yellow-200 flex-grow overflow-auto p-4 text-black">
+                <Suspense fallback=|| {
+                    view! { "Loading..." }
+                }>
+                    {move || match progress_resource.get() {
+                        Some(Ok(progress)) if progress.is_empty() => {
+                            view! { <p>"No words played yet."</p> }.into_any()
+                        }
+                        Some(Ok(progress)) => {
+                            view! {
+                                <div class="grid grid-cols-1 landscape:grid-cols-3 gap-4">
+                                    {progress
+                                        .into_iter()
+                                        .map(|language| view! { <LanguageCard progress=language /> })
+                                        .collect_view()}
+                                </div>
+                            }
+                                .into_any()
+                        }
+                        Some(Err(e)) => {
+                            leptos::logging::log!("Error loading progress: {:?}", e);
+                            view! {
+                                <p>
+                                    "Choose a learner or "
+                                    <a href="/account" class="underline">
+                                        "sign in"
+                                    </a> " to see progress."
+                                </p>
+                            }
+                                .into_any()
+                        }
+                        None => view! { "Loading..." }.into_any(),
+                    }}
+                </Suspense>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn LanguageCard(progress: LanguageProgress) -> impl IntoView {
+    let accuracy = format!("{:.0}%", progress.accuracy() * 100.0);
+
+    view! {
+        <div class="bg-white/50 rounded p-4 flex flex-col space-y-1">
+            <h2 class="text-lg font-bold">{progress.language}</h2>
+            <div class="flex justify-between">
+                <span>"Words"</span>
+                <span>{progress.words}</span>
+            </div>
+            <div class="flex justify-between">
+                <span>"Accuracy"</span>
+                <span>{accuracy}</span>
+            </div>
+            <div class="flex justify-between">
+                <span>"Score"</span>
+                <span>{progress.score}</span>
+            </div>
+            <div class="flex justify-between">
+                <span>"Current streak"</span>
+                <span>{progress.current_streak}</span>
+            </div>
+            <div class="flex justify-between">
+                <span>"Best streak"</span>
+                <span>{progress.best_streak}</span>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,86 @@
+use crate::game::GameContext;
+use crate::recognition::{self, RecognitionError, RecognizerContext};
+use leptos::prelude::*;
+
+#[derive(Debug, Clone, PartialEq)]
+enum ListenStatus {
+    Ready,
+    Listening,
+    Missed(String),
+}
+
+// Prompt shown after a correct spelling when "Say the word" is on
+#[component]
+pub fn SayTheWord() -> impl IntoView {
+    let game_context = use_context::<GameContext>().expect("GameContext should be provided");
+    let recognizer =
+        use_context::<RecognizerContext>().expect("RecognizerContext should be provided");
+    let state = game_context.state;
+    let (status, set_status) = signal(ListenStatus::Ready);
+
+    let game_context_skip = game_context.clone();
+    let listen = move |_| {
+        let (word, language) =
+            state.with_untracked(|s| (s.current_word.word.clone(), s.language.clone()));
+        let locale = language.locale().to_string();
+        let game_context = game_context.clone();
+        set_status.set(ListenStatus::Listening);
+        recognizer.0.listen(
+            &locale,
+            Box::new(
+                move |result: Result<Vec<String>, RecognitionError>| match result {
+                    Ok(transcripts)
+                        if recognition::is_lenient_match(&transcripts, &word, &language.code) =>
+                    {
+                        set_status.set(ListenStatus::Ready);
+                        game_context.record_pronunciation(true);
+                    }
+                    Ok(transcripts) => {
+                        let heard = transcripts.first().cloned().unwrap_or_default();
+                        set_status.set(ListenStatus::Missed(format!("Heard \"{heard}\"")));
+                    }
+                    // Nothing more the learner can do, so move on without the bonus
+                    Err(RecognitionError::Unsupported) => {
+                        set_status.set(ListenStatus::Ready);
+                        game_context.record_pronunciation(false);
+                    }
+                    Err(error) => set_status.set(ListenStatus::Missed(error.to_string())),
+                },
+            ),
+        );
+    };
+
+    view! {
+        <div
+            class="bg-teal-100 text-teal-900 p-3 flex items-center justify-center space-x-4"
+            class:hidden=move || !state.get().awaiting_pronunciation
+        >
+            <span class="text-lg">"Now say the word!"</span>
+            <button
+                on:click=listen
+                disabled=move || status.get() == ListenStatus::Listening
+                class="bg-teal-600 text-white px-3 py-1 rounded hover:bg-teal-700 disabled:opacity-50"
+            >
+                {move || match status.get() {
+                    ListenStatus::Listening => "Listening...",
+                    ListenStatus::Ready | ListenStatus::Missed(_) => "🎤 Speak",
+                }}
+            </button>
+            {move || match status.get() {
+                ListenStatus::Missed(message) => {
+                    Some(view! { <span class="text-sm">{message}" - try again"</span> })
+                }
+                ListenStatus::Ready | ListenStatus::Listening => None,
+            }}
+            <button
+                on:click=move |_| {
+                    set_status.set(ListenStatus::Ready);
+                    game_context_skip.record_pronunciation(false);
+                }
+                class="underline text-sm"
+            >
+                "Skip"
+            </button>
+        </div>
+    }
+}
@@ -0,0 +1,283 @@
+use crate::database::get_languages;
+use crate::game::{Difficulty, GameContext, LetterSpeech, PromptMode};
+use crate::normalize::LetterCase;
+use crate::recognition::RecognizerContext;
+use crate::streak::DAILY_GOALS;
+use leptos::prelude::*;
+
+#[component]
+pub fn SettingsMenu() -> impl IntoView {
+    let game_context = use_context::<GameContext>().expect("GameContext should be provided");
+    let settings = game_context.settings;
+    let current_language = game_context.current_language;
+    let (is_open, set_is_open) = signal(false);
+    let languages_resource = OnceResource::new(get_languages());
+    // Only offer "Say the word" where the browser can listen; checked on the client
+    let recognizer = use_context::<RecognizerContext>();
+    let (can_listen, set_can_listen) = signal(false);
+    Effect::new(move || {
+        set_can_listen.set(recognizer.as_ref().is_some_and(|r| r.0.is_supported()));
+    });
+
+    view! {
+        <div class="relative">
+            <button
+                on:click=move |_| set_is_open.update(|open| *open = !*open)
+                class="text-xl hover:bg-teal-600 p-2 rounded"
+            >
+                <img src="/icons/menu.svg" alt="Menu" class="w-6 h-6" />
+            </button>
+            <div
+                class="absolute end-0 mt-2 w-64 bg-teal-800 rounded shadow-lg p-3 z-10 flex flex-col space-y-2"
+                class:hidden=move || !is_open.get()
+            >
+                <label class="flex items-center justify-between space-x-2">
+                    <span>"Difficulty"</span>
+                    <select
+                        class="bg-teal-700 text-white px-2 py-1 rounded border border-teal-600"
+                        prop:value=move || settings.get().difficulty.label()
+                        on:change=move |ev| {
+                            if let Some(difficulty) = Difficulty::from_label(&event_target_value(&ev)) {
+                                game_context.set_difficulty(difficulty);
+                            }
+                        }
+                    >
+                        {Difficulty::ALL
+                            .into_iter()
+                            .map(|difficulty| {
+                                view! {
+                                    <option value=difficulty.label()>{difficulty.label()}</option>
+                                }
+                            })
+                            .collect_view()}
+                    </select>
+                </label>
+                <label class="flex items-center justify-between space-x-2">
+                    <span>"Prompt"</span>
+                    <select
+                        class="bg-teal-700 text-white px-2 py-1 rounded border border-teal-600"
+                        prop:value=move || settings.get().prompt.label()
+                        on:change=move |ev| {
+                            if let Some(prompt) = PromptMode::from_label(&event_target_value(&ev)) {
+                                settings.update(|s| s.prompt = prompt);
+                            }
+                        }
+                    >
+                        {PromptMode::ALL
+                            .into_iter()
+                            .map(|prompt| {
+                                view! { <option value=prompt.label()>{prompt.label()}</option> }
+                            })
+                            .collect_view()}
+                    </select>
+                </label>
+                <Suspense fallback=|| ()>
+                    <label class="flex items-center justify-between space-x-2">
+                        <span>"Translation"</span>
+                        <select
+                            class="bg-teal-700 text-white px-2 py-1 rounded border border-teal-600"
+                            prop:value=move || settings.get().translation_language.unwrap_or_default()
+                            on:change=move |ev| {
+                                let code = event_target_value(&ev);
+                                settings
+                                    .update(|s| {
+                                        s.translation_language = (!code.is_empty()).then_some(code);
+                                    });
+                            }
+                        >
+                            <option value="">"Off"</option>
+                            {move || {
+                                languages_resource
+                                    .get()
+                                    .and_then(Result::ok)
+                                    .unwrap_or_default()
+                                    .into_iter()
+                                    .map(|language| {
+                                        view! {
+                                            <option value=language.code.clone()>{language.name}</option>
+                                        }
+                                    })
+                                    .collect_view()
+                            }}
+                        </select>
+                    </label>
+                </Suspense>
+                <label class="flex items-center justify-between space-x-2">
+                    <span>"Letters"</span>
+                    <select
+                        class="bg-teal-700 text-white px-2 py-1 rounded border border-teal-600"
+                        prop:value=move || settings.get().letter_case.label()
+                        on:change=move |ev| {
+                            if let Some(letter_case) = LetterCase::from_label(&event_target_value(&ev)) {
+                                settings.update(|s| s.letter_case = letter_case);
+                            }
+                        }
+                    >
+                        {LetterCase::ALL
+                            .into_iter()
+                            .map(|letter_case| {
+                                view! {
+                                    <option value=letter_case.label()>{letter_case.label()}</option>
+                                }
+                            })
+                            .collect_view()}
+                    </select>
+                </label>
+                <label class="flex items-center justify-between space-x-2">
+                    <span>"Tiles say"</span>
+                    <select
+                        class="bg-teal-700 text-white px-2 py-1 rounded border border-teal-600"
+                        prop:value=move || settings.get().letter_speech.label()
+                        on:change=move |ev| {
+                            if let Some(letter_speech) = LetterSpeech::from_label(
+                                &event_target_value(&ev),
+                            ) {
+                                settings.update(|s| s.letter_speech = letter_speech);
+                            }
+                        }
+                    >
+                        {LetterSpeech::ALL
+                            .into_iter()
+                            .map(|letter_speech| {
+                                view! {
+                                    <option value=letter_speech.label()>{letter_speech.label()}</option>
+                                }
+                            })
+                            .collect_view()}
+                    </select>
+                </label>
+                <SpeechSlider
+                    label="Speech rate"
+                    min=0.5
+                    max=2.0
+                    value=Signal::derive(move || settings.get().speech.rate)
+                    on_change=move |rate| settings.update(|s| s.speech.rate = rate)
+                />
+                <SpeechSlider
+                    label="Pitch"
+                    min=0.0
+                    max=2.0
+                    value=Signal::derive(move || settings.get().speech.pitch)
+                    on_change=move |pitch| settings.update(|s| s.speech.pitch = pitch)
+                />
+                <SpeechSlider
+                    label="Volume"
+                    min=0.0
+                    max=1.0
+                    value=Signal::derive(move || settings.get().speech.volume)
+                    on_change=move |volume| settings.update(|s| s.speech.volume = volume)
+                />
+                <label class="flex items-center justify-between space-x-2">
+                    <span>"Daily goal"</span>
+                    <select
+                        class="bg-teal-700 text-white px-2 py-1 rounded border border-teal-600"
+                        prop:value=move || settings.get().daily_goal.to_string()
+                        on:change=move |ev| {
+                            if let Ok(goal) = event_target_value(&ev).parse::<u32>() {
+                                settings.update(|s| s.daily_goal = goal);
+                            }
+                        }
+                    >
+                        {DAILY_GOALS
+                            .into_iter()
+                            .map(|goal| {
+                                let label = match goal {
+                                    0 => "Off".to_string(),
+                                    goal => format!("{goal} words"),
+                                };
+                                view! { <option value=goal.to_string()>{label}</option> }
+                            })
+                            .collect_view()}
+                    </select>
+                </label>
+                <label class="flex items-center space-x-2">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || settings.get().reminders
+                        on:change=move |ev| {
+                            let checked = event_target_checked(&ev);
+                            settings.update(|s| s.reminders = checked);
+                        }
+                    />
+                    <span>"Email practice reminders"</span>
+                </label>
+                <label class="flex items-center space-x-2">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || settings.get().weak_letters
+                        on:change=move |ev| {
+                            let checked = event_target_checked(&ev);
+                            settings.update(|s| s.weak_letters = checked);
+                        }
+                    />
+                    <span>"Practice weak letters"</span>
+                </label>
+                <Show when=move || can_listen.get()>
+                    <label class="flex items-center space-x-2">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || settings.get().say_the_word
+                            on:change=move |ev| {
+                                let checked = event_target_checked(&ev);
+                                settings.update(|s| s.say_the_word = checked);
+                            }
+                        />
+                        <span>"Say the word"</span>
+                    </label>
+                </Show>
+                <Show when=move || current_language.get().code == "ar">
+                    <label class="flex items-center space-x-2">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || settings.get().contextual_forms
+                            on:change=move |ev| {
+                                let checked = event_target_checked(&ev);
+                                settings.update(|s| s.contextual_forms = checked);
+                            }
+                        />
+                        <span>"Contextual letter forms"</span>
+                    </label>
+                </Show>
+                <a
+                    href=move || {
+                        format!(
+                            "/leaderboard?lang={}&mode={}",
+                            current_language.get().code,
+                            settings.get().prompt.code(),
+                        )
+                    }
+                    class="hover:underline"
+                >
+                    "🏆 Leaderboard"
+                </a>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn SpeechSlider(
+    label: &'static str,
+    min: f32,
+    max: f32,
+    value: Signal<f32>,
+    on_change: impl Fn(f32) + 'static,
+) -> impl IntoView {
+    view! {
+        <label class="flex items-center justify-between space-x-2">
+            <span>{label}</span>
+            <input
+                type="range"
+                min=min
+                max=max
+                step=0.1
+                prop:value=move || value.get()
+                on:input=move |ev| {
+                    if let Ok(value) = event_target_value(&ev).parse::<f32>() {
+                        on_change(value);
+                    }
+                }
+            />
+        </label>
+    }
+}
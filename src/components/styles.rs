@@ -0,0 +1,3 @@
+// Tailwind classes shared by the forms on the account, profile and admin pages
+pub const INPUT_CLASS: &str = "bg-white text-black px-2 py-1 rounded border border-teal-600 w-full";
+pub const BUTTON_CLASS: &str = "bg-teal-600 text-white px-2 py-1 rounded hover:bg-teal-700";
@@ -1,8 +1,16 @@
 use std::collections::HashSet;
+use std::fmt;
 
 use leptos::prelude::*;
+use leptos::server_fn::codec::GetUrl;
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "ssr")]
+use crate::auth::{constant_time_eq, cookie, session_token, set_cookie, sha256};
+use crate::language_pack::PackFormat;
+use crate::validation::{LanguageReport, ValidationReport};
+use crate::{arabic, indic, normalize};
+
 #[cfg(feature = "ssr")]
 const DEFAULT_LANGUAGE_ID: u32 = 1;
 
@@ -13,6 +21,15 @@ pub struct Language {
     pub name_other: Option<String>,
     pub code: String,
     pub strip_diacritics: bool,
+    pub direction: String,
+    pub speech_locale: Option<String>,
+}
+
+impl Language {
+    // BCP-47 locale used to pick a speech voice, falling back to the bare language code
+    pub fn locale(&self) -> &str {
+        self.speech_locale.as_deref().unwrap_or(&self.code)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx_d1::FromRow)]
@@ -23,6 +40,46 @@ pub struct Letter {
     pub regular: Option<bool>,
     pub hidden: Option<bool>,
     pub name_en: Option<String>,
+    pub audio_url: Option<String>,
+    // Name of the letter as said in its own language, e.g. "bee" or "alif"
+    pub spoken_name: Option<String>,
+    // Text that makes the speech engine say the letter's sound, e.g. "buh"
+    pub phoneme: Option<String>,
+    // Position in the alphabet; letters without one follow in insertion order
+    pub sort_order: Option<u32>,
+}
+
+// Meaning of a word in another language, shown as a gloss or used as the prompt
+#[derive(Default, Debug, Clone, Serialize, Deserialize, sqlx_d1::FromRow)]
+pub struct WordTranslation {
+    pub id: u32,
+    pub word_id: u32,
+    pub language_id: u32,
+    pub translation: String,
+}
+
+// How a word is split into tiles: single letters (jamo for Korean) or whole syllable blocks
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TileMode {
+    #[default]
+    Letters,
+    Syllables,
+}
+
+impl TileMode {
+    pub const ALL: [TileMode; 2] = [TileMode::Letters, TileMode::Syllables];
+
+    // Stored with game sessions
+    pub fn code(&self) -> &'static str {
+        match self {
+            TileMode::Letters => "letters",
+            TileMode::Syllables => "syllables",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.code() == code)
+    }
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize, sqlx_d1::FromRow)]
@@ -30,6 +87,11 @@ pub struct Word {
     pub id: u32,
     pub word: String,
     pub language_id: u32,
+    pub audio_url: Option<String>,
+    // Picture prompt for pre-readers; an image is preferred over the emoji
+    pub image_url: Option<String>,
+    pub emoji: Option<String>,
+    pub category: Option<String>,
 }
 
 impl Word {
@@ -37,48 +99,153 @@ impl Word {
         Self::default()
     }
 
-    // Create single word and remove diacritics after loading from database
-    pub fn post_process(&self, lang: &Language) -> String {
-        let word = self
-            .word
-            .as_str()
-            .split_whitespace()
-            .next()
-            .map(|s| s.to_string())
-            .expect("word should always have non-empty characters");
-        match (lang.code.as_str(), lang.strip_diacritics) {
-            ("ar", true) => tashkil::remove(&word).to_string(),
+    // Normalize to NFC, collapse runs of whitespace and remove diacritics after loading from database
+    pub fn post_process(&self, lang: &Language) -> Result<String, WordError> {
+        let word = validate_word(&self.word)?;
+        Ok(match lang.code.as_str() {
+            "ar" => arabic::post_process(&word, lang.strip_diacritics),
             _ => word,
-        }
+        })
+    }
+
+    // Each letter of the word with the contextual form it takes at that position, in order,
+    // so a letter used more than once keeps a form for every position
+    pub fn contextual_forms(&self) -> Vec<(String, String)> {
+        arabic::positional_forms(&self.word)
+            .into_iter()
+            .map(|(letter, form)| {
+                let glyph = arabic::presentation_form(letter, form);
+                (letter.to_string(), glyph.to_string())
+            })
+            .collect()
     }
 
     // Create HashSet of letters to seed the grid before adding distractor letters
-    pub fn letters_for_grid(&self) -> HashSet<String> {
-        let letters = decompose(self.word.clone());
-        letters.into_iter().map(|c| c.to_string()).collect()
+    pub fn letters_for_grid(&self, mode: TileMode) -> HashSet<String> {
+        decompose(self.word.clone(), mode).into_iter().collect()
     }
 
-    pub fn len(&self) -> usize {
-        let letters = decompose(self.word.clone());
+    pub fn len(&self, mode: TileMode) -> usize {
+        let letters = decompose(self.word.clone(), mode);
         letters.len()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.len() == 0
+        self.len(TileMode::Letters) == 0
+    }
+}
+
+// Characters that join the words of a phrase, each spelled with its own tile
+pub const SEPARATORS: [char; 2] = [' ', '-'];
+
+pub fn is_separator(tile: &str) -> bool {
+    let mut chars = tile.chars();
+    chars.next().is_some_and(|c| SEPARATORS.contains(&c)) && chars.next().is_none()
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum WordError {
+    Empty,
+    // A phrase starts or ends with a separator, or has two in a row
+    StraySeparator(String),
+}
+
+impl fmt::Display for WordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WordError::Empty => write!(f, "Word is empty"),
+            WordError::StraySeparator(word) => write!(f, "Misplaced space or hyphen in \"{word}\""),
+        }
+    }
+}
+
+// Check a word or phrase before it is stored or played, returning it in NFC with single spaces
+pub fn validate_word(word: &str) -> Result<String, WordError> {
+    let word = normalize::nfc(word)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if word.is_empty() {
+        return Err(WordError::Empty);
+    }
+    let has_stray_separator = word.starts_with(SEPARATORS)
+        || word.ends_with(SEPARATORS)
+        || word.split(SEPARATORS).any(str::is_empty);
+    if has_stray_separator {
+        return Err(WordError::StraySeparator(word));
+    }
+    Ok(word)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LetterError {
+    Empty,
+    Separator,
+    // The text would be dealt as more than one tile
+    MultipleTiles(Vec<String>),
+}
+
+impl fmt::Display for LetterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LetterError::Empty => write!(f, "Letter is empty"),
+            LetterError::Separator => write!(f, "Spaces and hyphens are not letters"),
+            LetterError::MultipleTiles(tiles) => {
+                write!(f, "Letter splits into several tiles: {}", tiles.join(" + "))
+            }
+        }
+    }
+}
+
+// A letter must be exactly one tile under the game's own segmentation. Korean jamo are kept
+// as written since decompose only splits composed syllable blocks.
+pub fn validate_letter(letter: &str) -> Result<String, LetterError> {
+    let letter = normalize::nfc(letter.trim());
+    if letter.is_empty() {
+        return Err(LetterError::Empty);
+    }
+    if letter.contains(SEPARATORS) {
+        return Err(LetterError::Separator);
+    }
+    let is_jamo = letter
+        .chars()
+        .all(|c| ('\u{3131}'..='\u{318E}').contains(&c));
+    let tiles = decompose(letter.clone(), TileMode::Letters);
+    if !is_jamo && tiles.len() > 1 {
+        return Err(LetterError::MultipleTiles(tiles));
     }
+    Ok(letter)
 }
 
-pub fn decompose(word: String) -> Vec<char> {
+// Split a word or phrase into the tiles used to spell it, a tile may be more than one char
+pub fn decompose(word: String, mode: TileMode) -> Vec<String> {
+    let mut tiles = Vec::new();
+    let mut rest = word.as_str();
+    while let Some(index) = rest.find(SEPARATORS) {
+        tiles.extend(decompose_segment(&rest[..index], mode));
+        tiles.push(rest[index..index + 1].to_string());
+        rest = &rest[index + 1..];
+    }
+    tiles.extend(decompose_segment(rest, mode));
+    tiles
+}
+
+fn decompose_segment(word: &str, mode: TileMode) -> Vec<String> {
     match word.chars().next() {
-        Some(c) if rustkorean::check_korean(c) => hangeul::decompose(&word)
+        Some(c) if rustkorean::check_korean(c) && mode == TileMode::Syllables => {
+            word.chars().map(|c| c.to_string()).collect()
+        }
+        Some(c) if rustkorean::check_korean(c) => hangeul::decompose(word)
             .into_iter()
             .flat_map(|block| match block {
                 Ok((first, second, Some(third))) => vec![first, second, third],
                 Ok((first, second, None)) => vec![first, second],
                 _ => vec![],
             })
+            .map(|c| c.to_string())
             .collect(),
-        _ => word.chars().collect(),
+        Some(c) if indic::is_indic(c) => indic::segment(word),
+        _ => word.chars().map(|c| c.to_string()).collect(),
     }
 }
 
@@ -94,7 +261,7 @@ pub async fn get_languages() -> Result<Vec<Language>, ServerFnError> {
 
     let languages = sqlx_d1::query_as!(
         Language,
-        "SELECT id, name, name_other, code, strip_diacritics FROM Languages"
+        "SELECT id, name, name_other, code, strip_diacritics, direction, speech_locale FROM Languages"
     )
     .fetch_all(&conn)
     .await
@@ -114,11 +281,20 @@ pub async fn get_letters_for_language(language: Language) -> Result<Vec<Letter>,
     let conn = sqlx_d1::D1Connection::new(d1);
 
     let letters = sqlx_d1::query_as!(Letter,
-        "SELECT id, letter, language_id, regular, hidden, name_en FROM Letters WHERE language_id = ?",
+        "SELECT id, letter, language_id, regular, hidden, name_en, audio_url, spoken_name, phoneme, sort_order FROM Letters WHERE language_id = ? ORDER BY sort_order IS NULL, sort_order, id",
         language.id
     )
     .fetch_all(&conn)
     .await
+    .map(|letters| {
+        letters
+            .into_iter()
+            .map(|mut letter| {
+                letter.letter = normalize::nfc(&letter.letter);
+                letter
+            })
+            .collect::<Vec<_>>()
+    })
     .map_err(|e| worker::Error::RustError(e.to_string()))?;
 
     Ok(letters)
@@ -136,7 +312,7 @@ pub async fn get_words_for_language(language: Language) -> Result<Vec<Word>, Ser
 
     let words = sqlx_d1::query_as!(
         Word,
-        "SELECT id, word, language_id FROM Words WHERE language_id = ?",
+        "SELECT id, word, language_id, audio_url, image_url, emoji, category FROM Words WHERE language_id = ?",
         language.id
     )
     .fetch_all(&conn)
@@ -146,32 +322,620 @@ pub async fn get_words_for_language(language: Language) -> Result<Vec<Word>, Ser
     Ok(words)
 }
 
+// Choose the learner's next word with the spaced repetition scheduler. Guests, and learners
+// who have not played this language yet, get a random word. With `weak_letters` the choice is
+// narrowed to words using the learner's weakest letters, when there are any.
 #[server]
-pub async fn get_random_word_for_language(language: Language) -> Result<Word, ServerFnError> {
+pub async fn get_next_word_for_language(
+    language: Language,
+    profile_id: Option<String>,
+    previous_word_id: Option<u32>,
+    weak_letters: bool,
+) -> Result<Word, ServerFnError> {
     use axum::Extension;
     use std::sync::Arc;
     use worker::Env;
 
+    use crate::scheduler::{self, Review};
+
     let Extension::<Arc<Env>>(env) = leptos_axum::extract().await?;
     let d1 = env.d1("alphabet_game_stg")?;
     let conn = sqlx_d1::D1Connection::new(d1);
 
-    let word = sqlx_d1::query_as!(
+    let words = sqlx_d1::query_as!(
         Word,
-        "SELECT id, word, language_id FROM Words WHERE language_id = ? ORDER BY RANDOM()",
+        "SELECT id, word, language_id, audio_url, image_url, emoji, category FROM Words WHERE language_id = ? AND trim(word) != ''",
         language.id
     )
-    .fetch_one(&conn)
+    .fetch_all(&conn)
     .await
-    .map(|mut word| {
-        word.word = word.post_process(&language);
-        word
-    })
     .map_err(|e| worker::Error::RustError(e.to_string()))?;
 
+    let player = crate::progress::Player {
+        user_id: crate::auth::get_current_user().await?.map(|user| user.id),
+        profile_id: profile_id.clone(),
+    };
+    // A profile's own results, or the account's when playing without a profile
+    let reviews: Vec<Review> = if player.is_guest() {
+        vec![]
+    } else {
+        sqlx_d1::query!(
+            "SELECT word_id, attempts, correct, CAST(strftime('%s', created_at) AS INTEGER) AS at FROM GameResults WHERE language_id = ? AND (profile_id = ? OR (? IS NULL AND user_id = ?)) ORDER BY id",
+            language.id,
+            player.profile_id,
+            player.profile_id,
+            player.user_id
+        )
+        .fetch_all(&conn)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?
+        .into_iter()
+        .map(|row| Review {
+            word_id: row.word_id as u32,
+            correct: row.correct,
+            attempts: row.attempts as i32,
+            at: row.at.unwrap_or_default(),
+        })
+        .collect()
+    };
+
+    let mut word_ids: Vec<u32> = words.iter().map(|word| word.id).collect();
+    if weak_letters {
+        let mastery = crate::progress::get_letter_mastery(language.id, profile_id.clone()).await?;
+        let weakest = crate::mastery::weakest_letters(&mastery, crate::mastery::WEAK_LETTER_COUNT);
+        if !weakest.is_empty() {
+            let letters = get_letters_for_language(language.clone()).await?;
+            let practice =
+                crate::mastery::words_with_letters(&words, &letters, &weakest, &language.code);
+            if !practice.is_empty() {
+                word_ids = practice;
+            }
+        }
+    }
+
+    let cards = scheduler::schedule(&reviews);
+    let now = (worker::Date::now().as_millis() / 1000) as i64;
+    let next_id = scheduler::next_word(&word_ids, &cards, now, previous_word_id, &mut rand::rng())
+        .ok_or_else(|| ServerFnError::new(format!("No words for {}", language.code)))?;
+    let mut word = words
+        .into_iter()
+        .find(|word| word.id == next_id)
+        .ok_or_else(|| ServerFnError::new(format!("Word {next_id} not found")))?;
+    word.word = word.post_process(&language).map_err(ServerFnError::new)?;
+
     Ok(word)
 }
 
+#[server]
+pub async fn get_translation(
+    word_id: u32,
+    language_code: String,
+) -> Result<Option<String>, ServerFnError> {
+    use axum::Extension;
+    use std::sync::Arc;
+    use worker::Env;
+
+    let Extension::<Arc<Env>>(env) = leptos_axum::extract().await?;
+    let d1 = env.d1("alphabet_game_stg")?;
+    let conn = sqlx_d1::D1Connection::new(d1);
+
+    let translation = sqlx_d1::query_as!(
+        WordTranslation,
+        "SELECT t.id, t.word_id, t.language_id, t.translation FROM WordTranslations t JOIN Languages l ON l.id = t.language_id WHERE t.word_id = ? AND l.code = ?",
+        word_id,
+        language_code
+    )
+    .fetch_optional(&conn)
+    .await
+    .map_err(|e| worker::Error::RustError(e.to_string()))?;
+
+    Ok(translation.map(|t| t.translation))
+}
+
+// Report content problems for every language as JSON, e.g.
+// `curl -H "Authorization: Bearer $ADMIN_TOKEN" /api/validate_content`
+#[server(endpoint = "validate_content", input = GetUrl)]
+pub async fn validate_content() -> Result<ValidationReport, ServerFnError> {
+    require_admin().await?;
+    let mut reports = Vec::new();
+    for language in get_languages().await? {
+        let letters = get_letters_for_language(language.clone()).await?;
+        let words = get_words_for_language(language.clone()).await?;
+        reports.push(crate::validation::validate_language(
+            &language, &letters, &words,
+        ));
+    }
+    Ok(ValidationReport::new(reports))
+}
+
+#[cfg(feature = "ssr")]
+fn check_admin_token(env: &worker::Env, token: &str) -> Result<(), ServerFnError> {
+    let expected = env
+        .secret("ADMIN_TOKEN")
+        .map(|secret| secret.to_string())
+        .map_err(|_| ServerFnError::new("ADMIN_TOKEN is not configured"))?;
+    // Compared as digests so the comparison takes the same time whatever the token's length
+    let matches = constant_time_eq(sha256(token).as_bytes(), sha256(&expected).as_bytes());
+    if expected.is_empty() || !matches {
+        return Err(ServerFnError::new("Invalid admin token"));
+    }
+    Ok(())
+}
+
+// Validate a language pack and upsert it by language code, letter and word in one D1 batch,
+// which runs as a transaction. Rows missing from the pack are left in place. Like the other
+// admin endpoints it takes the admin session cookie or `Authorization: Bearer $ADMIN_TOKEN`.
+#[server(endpoint = "import_language_pack")]
+pub async fn import_language_pack(
+    format: PackFormat,
+    data: String,
+) -> Result<LanguageReport, ServerFnError> {
+    let env = require_admin().await?;
+    let pack =
+        crate::language_pack::LanguagePack::parse(format, &data).map_err(ServerFnError::new)?;
+    let report = pack.validate().map_err(ServerFnError::new)?;
+    let d1 = env.d1("alphabet_game_stg")?;
+    let statements = pack_statements(&d1, &pack)?;
+    // The D1 bindings are JavaScript objects, so the batch is wrapped to keep the future Send
+    worker::send::SendFuture::new(async move { d1.batch(statements).await }).await?;
+
+    Ok(report)
+}
+
+// Upserts for the language, its letters and its words, by code, letter and word
+#[cfg(feature = "ssr")]
+fn pack_statements(
+    d1: &worker::D1Database,
+    pack: &crate::language_pack::LanguagePack,
+) -> worker::Result<Vec<worker::D1PreparedStatement>> {
+    use wasm_bindgen::JsValue;
+
+    let language = &pack.language;
+    let code = JsValue::from(language.code.as_str());
+    let language_values = [
+        JsValue::from(language.name.as_str()),
+        JsValue::from(language.name_other.clone()),
+        JsValue::from(language.strip_diacritics),
+        JsValue::from(language.direction.as_str()),
+        JsValue::from(language.speech_locale.clone()),
+        code.clone(),
+    ];
+    let mut statements = vec![
+        d1.prepare(
+            "UPDATE Languages SET name = ?1, name_other = ?2, strip_diacritics = ?3, direction = ?4, speech_locale = ?5 WHERE code = ?6",
+        )
+        .bind(&language_values)?,
+        d1.prepare(
+            "INSERT INTO Languages (name, name_other, strip_diacritics, direction, speech_locale, code) SELECT ?1, ?2, ?3, ?4, ?5, ?6 WHERE NOT EXISTS (SELECT 1 FROM Languages WHERE code = ?6)",
+        )
+        .bind(&language_values)?,
+    ];
+    for letter in &pack.letters {
+        let values = [
+            JsValue::from(letter.regular),
+            JsValue::from(letter.hidden),
+            JsValue::from(letter.name_en.clone()),
+            JsValue::from(letter.audio_url.clone()),
+            JsValue::from(letter.spoken_name.clone()),
+            JsValue::from(letter.phoneme.clone()),
+            JsValue::from(normalize::nfc(&letter.letter)),
+            code.clone(),
+            JsValue::from(letter.sort_order),
+        ];
+        statements.push(
+            d1.prepare(
+                "UPDATE Letters SET regular = ?1, hidden = ?2, name_en = ?3, audio_url = ?4, spoken_name = ?5, phoneme = ?6, sort_order = ?9 WHERE letter = ?7 AND language_id = (SELECT id FROM Languages WHERE code = ?8)",
+            )
+            .bind(&values)?,
+        );
+        statements.push(
+            d1.prepare(
+                "INSERT INTO Letters (regular, hidden, name_en, audio_url, spoken_name, phoneme, letter, language_id, sort_order) SELECT ?1, ?2, ?3, ?4, ?5, ?6, ?7, l.id, ?9 FROM Languages l WHERE l.code = ?8 AND NOT EXISTS (SELECT 1 FROM Letters WHERE letter = ?7 AND language_id = l.id)",
+            )
+            .bind(&values)?,
+        );
+    }
+    for word in &pack.words {
+        let values = [
+            JsValue::from(word.category.clone()),
+            JsValue::from(word.audio_url.clone()),
+            JsValue::from(word.image_url.clone()),
+            JsValue::from(word.emoji.clone()),
+            // validate() has already rejected words that fail validate_word
+            JsValue::from(validate_word(&word.word).unwrap_or_default()),
+            code.clone(),
+        ];
+        statements.push(
+            d1.prepare(
+                "UPDATE Words SET category = ?1, audio_url = ?2, image_url = ?3, emoji = ?4 WHERE word = ?5 AND language_id = (SELECT id FROM Languages WHERE code = ?6)",
+            )
+            .bind(&values)?,
+        );
+        statements.push(
+            d1.prepare(
+                "INSERT INTO Words (category, audio_url, image_url, emoji, word, language_id) SELECT ?1, ?2, ?3, ?4, ?5, l.id FROM Languages l WHERE l.code = ?6 AND NOT EXISTS (SELECT 1 FROM Words WHERE word = ?5 AND language_id = l.id)",
+            )
+            .bind(&values)?,
+        );
+    }
+    Ok(statements)
+}
+
+// Download a language pack for backups, e.g.
+// `curl -H "Authorization: Bearer $ADMIN_TOKEN" "/api/export_language_pack?code=hi&format=csv"`
+#[server(endpoint = "export_language_pack", input = GetUrl)]
+pub async fn export_language_pack(
+    code: String,
+    format: PackFormat,
+) -> Result<String, ServerFnError> {
+    require_admin().await?;
+    let language = get_languages()
+        .await?
+        .into_iter()
+        .find(|language| language.code == code)
+        .ok_or_else(|| ServerFnError::new(format!("No language with code {code}")))?;
+    let letters = get_letters_for_language(language.clone()).await?;
+    let words = get_words_for_language(language.clone()).await?;
+    crate::language_pack::LanguagePack::from_parts(&language, letters, words)
+        .write(format)
+        .map_err(ServerFnError::new)
+}
+
+#[cfg(feature = "ssr")]
+const ADMIN_COOKIE: &str = "admin_session";
+
+// Environment for a request that carries an admin session cookie, or the admin token as
+// `Authorization: Bearer <token>` from scripts
+#[cfg(feature = "ssr")]
+async fn require_admin() -> Result<std::sync::Arc<worker::Env>, ServerFnError> {
+    use axum::Extension;
+    use axum::http::{HeaderMap, header::AUTHORIZATION};
+    use std::sync::Arc;
+    use worker::Env;
+
+    let Extension::<Arc<Env>>(env) = leptos_axum::extract().await?;
+    let headers: HeaderMap = leptos_axum::extract().await?;
+    let bearer = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    if let Some(token) = bearer {
+        check_admin_token(&env, token)?;
+        return Ok(env);
+    }
+
+    let not_admin = || ServerFnError::new("Not signed in as admin");
+    let token = cookie(&headers, ADMIN_COOKIE).ok_or_else(not_admin)?;
+    let conn = sqlx_d1::D1Connection::new(env.d1("alphabet_game_stg")?);
+    let session_id = sha256(&token);
+    sqlx_d1::query!(
+        "SELECT id FROM AdminSessions WHERE id = ? AND expires_at > datetime('now')",
+        session_id
+    )
+    .fetch_optional(&conn)
+    .await
+    .map_err(|e| worker::Error::RustError(e.to_string()))?
+    .ok_or_else(not_admin)?;
+    Ok(env)
+}
+
+// Connection for a request from an admin
+#[cfg(feature = "ssr")]
+async fn admin_connection() -> Result<sqlx_d1::D1Connection, ServerFnError> {
+    let d1 = require_admin().await?.d1("alphabet_game_stg")?;
+    Ok(sqlx_d1::D1Connection::new(d1))
+}
+
+// Exchange the admin token for a day-long session, so the token itself never goes in a cookie
+#[server]
+pub async fn admin_login(token: String) -> Result<(), ServerFnError> {
+    use axum::Extension;
+    use std::sync::Arc;
+    use worker::Env;
+
+    let Extension::<Arc<Env>>(env) = leptos_axum::extract().await?;
+    check_admin_token(&env, &token)?;
+    let d1 = env.d1("alphabet_game_stg")?;
+    let conn = sqlx_d1::D1Connection::new(d1);
+    sqlx_d1::query!("DELETE FROM AdminSessions WHERE expires_at <= datetime('now')")
+        .execute(&conn)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?;
+    let session = session_token()?;
+    let session_id = sha256(&session);
+    sqlx_d1::query!(
+        "INSERT INTO AdminSessions (id, expires_at) VALUES (?, datetime('now', '+1 day'))",
+        session_id
+    )
+    .execute(&conn)
+    .await
+    .map_err(|e| worker::Error::RustError(e.to_string()))?;
+    set_cookie(format!(
+        "{ADMIN_COOKIE}={session}; Path=/; HttpOnly; Secure; SameSite=Strict; Max-Age=86400"
+    ))
+}
+
+#[server]
+pub async fn admin_logout() -> Result<(), ServerFnError> {
+    use axum::Extension;
+    use axum::http::HeaderMap;
+    use std::sync::Arc;
+    use worker::Env;
+
+    let Extension::<Arc<Env>>(env) = leptos_axum::extract().await?;
+    let headers: HeaderMap = leptos_axum::extract().await?;
+    if let Some(session) = cookie(&headers, ADMIN_COOKIE) {
+        let d1 = env.d1("alphabet_game_stg")?;
+        let conn = sqlx_d1::D1Connection::new(d1);
+        let session_id = sha256(&session);
+        sqlx_d1::query!("DELETE FROM AdminSessions WHERE id = ?", session_id)
+            .execute(&conn)
+            .await
+            .map_err(|e| worker::Error::RustError(e.to_string()))?;
+    }
+    set_cookie(format!(
+        "{ADMIN_COOKIE}=; Path=/; HttpOnly; Secure; SameSite=Strict; Max-Age=0"
+    ))
+}
+
+#[server]
+pub async fn is_admin() -> Result<bool, ServerFnError> {
+    Ok(admin_connection().await.is_ok())
+}
+
+// Insert when the id is 0, otherwise update; returns the row id
+#[server]
+pub async fn save_language(language: Language) -> Result<u32, ServerFnError> {
+    let conn = admin_connection().await?;
+    let code = language.code.trim();
+    if code.is_empty() || language.name.trim().is_empty() {
+        return Err(ServerFnError::new("Language code and name are required"));
+    }
+    if !matches!(language.direction.as_str(), "ltr" | "rtl") {
+        return Err(ServerFnError::new("Direction must be ltr or rtl"));
+    }
+
+    let id = if language.id == 0 {
+        sqlx_d1::query!(
+            "INSERT INTO Languages (name, name_other, code, strip_diacritics, direction, speech_locale) VALUES (?, ?, ?, ?, ?, ?) RETURNING id",
+            language.name,
+            language.name_other,
+            code,
+            language.strip_diacritics,
+            language.direction,
+            language.speech_locale
+        )
+        .fetch_one(&conn)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?
+        .id as u32
+    } else {
+        sqlx_d1::query!(
+            "UPDATE Languages SET name = ?, name_other = ?, code = ?, strip_diacritics = ?, direction = ?, speech_locale = ? WHERE id = ?",
+            language.name,
+            language.name_other,
+            code,
+            language.strip_diacritics,
+            language.direction,
+            language.speech_locale,
+            language.id
+        )
+        .execute(&conn)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?;
+        language.id
+    };
+
+    Ok(id)
+}
+
+// Languages are only removed once nothing refers to them: letters, words, translations into the
+// language or leaderboard scores
+#[server]
+pub async fn delete_language(id: u32) -> Result<(), ServerFnError> {
+    let conn = admin_connection().await?;
+    let in_use = sqlx_d1::query!(
+        "SELECT COUNT(*) AS count FROM Letters l WHERE l.language_id = ? UNION ALL SELECT COUNT(*) AS count FROM Words w WHERE w.language_id = ? UNION ALL SELECT COUNT(*) AS count FROM WordTranslations t WHERE t.language_id = ? UNION ALL SELECT COUNT(*) AS count FROM Leaderboard b WHERE b.language_id = ?",
+        id,
+        id,
+        id,
+        id
+    )
+    .fetch_all(&conn)
+    .await
+    .map_err(|e| worker::Error::RustError(e.to_string()))?
+    .iter()
+    .any(|row| row.count > 0);
+    if in_use {
+        return Err(ServerFnError::new(
+            "The language still has letters, words, translations into it or leaderboard scores",
+        ));
+    }
+
+    sqlx_d1::query!("DELETE FROM Languages WHERE id = ?", id)
+        .execute(&conn)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?;
+
+    Ok(())
+}
+
+#[server]
+pub async fn save_letter(letter: Letter) -> Result<u32, ServerFnError> {
+    let conn = admin_connection().await?;
+    let text = validate_letter(&letter.letter).map_err(ServerFnError::new)?;
+
+    let id = if letter.id == 0 {
+        sqlx_d1::query!(
+            "INSERT INTO Letters (letter, language_id, regular, hidden, name_en, audio_url, spoken_name, phoneme, sort_order) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?) RETURNING id",
+            text,
+            letter.language_id,
+            letter.regular,
+            letter.hidden,
+            letter.name_en,
+            letter.audio_url,
+            letter.spoken_name,
+            letter.phoneme,
+            letter.sort_order
+        )
+        .fetch_one(&conn)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?
+        .id as u32
+    } else {
+        sqlx_d1::query!(
+            "UPDATE Letters SET letter = ?, regular = ?, hidden = ?, name_en = ?, audio_url = ?, spoken_name = ?, phoneme = ?, sort_order = ? WHERE id = ?",
+            text,
+            letter.regular,
+            letter.hidden,
+            letter.name_en,
+            letter.audio_url,
+            letter.spoken_name,
+            letter.phoneme,
+            letter.sort_order,
+            letter.id
+        )
+        .execute(&conn)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?;
+        letter.id
+    };
+
+    Ok(id)
+}
+
+// Letters that have been played are kept, as learners' mastery and weak-letter drills are
+// worked out from their results; hide them from the alphabet instead
+#[server]
+pub async fn delete_letter(id: u32) -> Result<(), ServerFnError> {
+    let conn = admin_connection().await?;
+    let in_use = sqlx_d1::query!(
+        "SELECT COUNT(*) AS count FROM LetterResults WHERE letter_id = ?",
+        id
+    )
+    .fetch_one(&conn)
+    .await
+    .map_err(|e| worker::Error::RustError(e.to_string()))?
+    .count
+        > 0;
+    if in_use {
+        return Err(ServerFnError::new(
+            "Learners have played this letter, so it can only be hidden",
+        ));
+    }
+
+    sqlx_d1::query!("DELETE FROM Letters WHERE id = ?", id)
+        .execute(&conn)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?;
+
+    Ok(())
+}
+
+// Words are checked against the language's alphabet and existing words before saving
+#[server]
+pub async fn save_word(word: Word) -> Result<u32, ServerFnError> {
+    let conn = admin_connection().await?;
+    let language = get_languages()
+        .await?
+        .into_iter()
+        .find(|language| language.id == word.language_id)
+        .ok_or_else(|| ServerFnError::new("Unknown language"))?;
+    let text = validate_word(&word.word).map_err(ServerFnError::new)?;
+    let letters = get_letters_for_language(language.clone()).await?;
+    let existing = get_words_for_language(language.clone()).await?;
+    let issues = crate::validation::check_word(&language, &letters, &existing, &word);
+    if !issues.is_empty() {
+        let messages: Vec<String> = issues.iter().map(ToString::to_string).collect();
+        return Err(ServerFnError::new(messages.join("; ")));
+    }
+
+    let id = if word.id == 0 {
+        sqlx_d1::query!(
+            "INSERT INTO Words (word, language_id, audio_url, image_url, emoji, category) VALUES (?, ?, ?, ?, ?, ?) RETURNING id",
+            text,
+            word.language_id,
+            word.audio_url,
+            word.image_url,
+            word.emoji,
+            word.category
+        )
+        .fetch_one(&conn)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?
+        .id as u32
+    } else {
+        // A word stays in its language, which is the one it was checked against above
+        sqlx_d1::query!(
+            "UPDATE Words SET word = ?, audio_url = ?, image_url = ?, emoji = ?, category = ? WHERE id = ? AND language_id = ? RETURNING id",
+            text,
+            word.audio_url,
+            word.image_url,
+            word.emoji,
+            word.category,
+            word.id,
+            word.language_id
+        )
+        .fetch_optional(&conn)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?
+        .ok_or_else(|| ServerFnError::new(format!("No word {} in this language", word.id)))?
+        .id as u32
+    };
+
+    Ok(id)
+}
+
+// Words that have been played are kept, as learners' progress, streaks and review history are
+// worked out from their results. Translations belong to the word, so they are removed with it.
+#[server]
+pub async fn delete_word(id: u32) -> Result<(), ServerFnError> {
+    let env = require_admin().await?;
+    let conn = sqlx_d1::D1Connection::new(env.d1("alphabet_game_stg")?);
+    let in_use = sqlx_d1::query!(
+        "SELECT COUNT(*) AS count FROM GameResults WHERE word_id = ? UNION ALL SELECT COUNT(*) AS count FROM GameSessions WHERE word_id = ?",
+        id,
+        id
+    )
+    .fetch_all(&conn)
+    .await
+    .map_err(|e| worker::Error::RustError(e.to_string()))?
+    .iter()
+    .any(|row| row.count > 0);
+    if in_use {
+        return Err(ServerFnError::new(
+            "Learners have played this word, so it cannot be deleted",
+        ));
+    }
+
+    let d1 = env.d1("alphabet_game_stg")?;
+    let statements = delete_statements(
+        &d1,
+        &[
+            "DELETE FROM WordTranslations WHERE word_id = ?1",
+            "DELETE FROM Words WHERE id = ?1",
+        ],
+        id,
+    )?;
+    worker::send::SendFuture::new(async move { d1.batch(statements).await }).await?;
+
+    Ok(())
+}
+
+// A row and the rows that belong to it, deleted together in one D1 batch, which runs as a
+// transaction so the foreign keys hold throughout
+#[cfg(feature = "ssr")]
+fn delete_statements(
+    d1: &worker::D1Database,
+    queries: &[&str],
+    id: u32,
+) -> worker::Result<Vec<worker::D1PreparedStatement>> {
+    queries
+        .iter()
+        .map(|query| d1.prepare(*query).bind(&[id.into()]))
+        .collect()
+}
+
 #[server]
 pub async fn get_default_language() -> Result<Language, ServerFnError> {
     use axum::Extension;
@@ -184,7 +948,7 @@ pub async fn get_default_language() -> Result<Language, ServerFnError> {
 
     match sqlx_d1::query_as!(
         Language,
-        "SELECT id, name, name_other, code, strip_diacritics FROM Languages WHERE id = ?",
+        "SELECT id, name, name_other, code, strip_diacritics, direction, speech_locale FROM Languages WHERE id = ?",
         DEFAULT_LANGUAGE_ID
     )
     .fetch_one(&conn)
@@ -199,3 +963,32 @@ pub async fn get_default_language() -> Result<Language, ServerFnError> {
         Ok(default_langauage) => Ok(default_langauage),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contextual_forms_follow_each_position() {
+        let word = Word {
+            word: "سلسلة".to_string(),
+            ..Word::new()
+        };
+        let forms = word.contextual_forms();
+        let forms: Vec<(&str, &str)> = forms
+            .iter()
+            .map(|(letter, form)| (letter.as_str(), form.as_str()))
+            .collect();
+        // The first س is initial and the second medial
+        assert_eq!(
+            forms,
+            vec![
+                ("س", "\u{FEB3}"),
+                ("ل", "\u{FEE0}"),
+                ("س", "\u{FEB4}"),
+                ("ل", "\u{FEE0}"),
+                ("ة", "\u{FE94}"),
+            ]
+        );
+    }
+}
@@ -1,20 +1,34 @@
+use std::collections::HashMap;
+
 use leptos::prelude::*;
 use rand::seq::SliceRandom;
 use serde::{Deserialize, Serialize};
 
-use crate::database::{Language, Word};
+use crate::achievements::Achievement;
+use crate::database::{self, Language, Letter, TileMode, Word};
+use crate::normalize::{self, LetterCase};
+use crate::profile::Profile;
+use crate::session::{self, SessionState};
+use crate::speech::{self, SpeechError, SpeechSettings};
+use crate::streak::DailyStreak;
+use crate::{indic, korean};
 
 const GAME_GRID_SIZE: usize = 12;
+const PRONUNCIATION_BONUS: i32 = 5;
+const DEFAULT_TRANSLATION_LANGUAGE: &str = "en";
+const SPACE_LABEL: &str = "␣";
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct UserInput {
     pub word: String,
-    pub parts: Vec<char>,
+    pub parts: Vec<String>,
+    pub mode: TileMode,
 }
 
+// Canonically equivalent text is equal regardless of NFC or NFD encoding
 impl PartialEq<Word> for UserInput {
     fn eq(&self, other: &Word) -> bool {
-        self.word == other.word
+        normalize::nfc(&self.word) == normalize::nfc(&other.word)
     }
 }
 
@@ -23,27 +37,49 @@ impl UserInput {
         Self::default()
     }
 
+    pub fn with_mode(mode: TileMode) -> Self {
+        Self {
+            mode,
+            ..Self::default()
+        }
+    }
+
     pub fn add_letter(&mut self, letter: &str) {
-        for c in letter.chars() {
-            self.parts.push(c);
+        self.parts.push(letter.to_string());
+        self.word = self.compose();
+    }
+
+    pub fn remove_last_letter(&mut self) {
+        self.parts.pop();
+        self.word = self.compose();
+    }
+
+    // Build the answer one word of the phrase at a time so Korean jamo never compose across a
+    // space or hyphen tile
+    fn compose(&self) -> String {
+        // Syllable tiles are already composed blocks
+        if self.mode == TileMode::Syllables {
+            return self.parts.concat();
         }
-        if let Some(first_char) = self.word.chars().next()
-            && rustkorean::check_korean(first_char)
+        let mut word = String::new();
+        for segment in self
+            .parts
+            .split_inclusive(|part| database::is_separator(part))
         {
-            self.word = rustkorean::compose_korean(self.parts.clone());
-        } else {
-            self.word = self.parts.iter().collect();
+            let (separator, letters) = match segment.split_last() {
+                Some((last, letters)) if database::is_separator(last) => (Some(last), letters),
+                _ => (None, segment),
+            };
+            word.push_str(&compose_segment(letters));
+            word.extend(separator.map(String::as_str));
         }
+        word
     }
 
-    pub fn remove_last_letter(&mut self) {
-        if let Some(last_letter) = self.parts.pop()
-            && rustkorean::check_korean(last_letter)
-        {
-            self.word = rustkorean::compose_korean(self.parts.clone());
-        } else {
-            self.word = self.parts.iter().collect();
-        };
+    // Compare with the target word ignoring case using the language's case rules
+    pub fn matches(&self, word: &Word, lang_code: &str) -> bool {
+        normalize::normalize_answer(&self.word, lang_code)
+            == normalize::normalize_answer(&word.word, lang_code)
     }
 
     pub fn len(&self) -> usize {
@@ -55,19 +91,175 @@ impl UserInput {
     }
 }
 
+fn compose_segment(letters: &[String]) -> String {
+    let jamo: Vec<char> = letters.iter().flat_map(|part| part.chars()).collect();
+    if jamo.first().is_some_and(|&c| rustkorean::check_korean(c)) {
+        rustkorean::compose_korean(jamo)
+    } else {
+        jamo.into_iter().collect()
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Difficulty {
+    Beginner,
+    #[default]
+    Intermediate,
+    Advanced,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [
+        Difficulty::Beginner,
+        Difficulty::Intermediate,
+        Difficulty::Advanced,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Difficulty::Beginner => "Beginner",
+            Difficulty::Intermediate => "Intermediate",
+            Difficulty::Advanced => "Advanced",
+        }
+    }
+
+    pub fn from_label(label: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.label() == label)
+    }
+
+    // Beginners spell with whole syllable blocks where the script has them
+    pub fn tile_mode(&self) -> TileMode {
+        match self {
+            Difficulty::Beginner => TileMode::Syllables,
+            Difficulty::Intermediate | Difficulty::Advanced => TileMode::Letters,
+        }
+    }
+}
+
+// Whether tapping a tile says the letter's name ("bee") or its sound ("buh")
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LetterSpeech {
+    #[default]
+    Name,
+    Sound,
+}
+
+impl LetterSpeech {
+    pub const ALL: [LetterSpeech; 2] = [LetterSpeech::Name, LetterSpeech::Sound];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LetterSpeech::Name => "Letter name",
+            LetterSpeech::Sound => "Letter sound",
+        }
+    }
+
+    pub fn from_label(label: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.label() == label)
+    }
+}
+
+// How the target word is presented before the learner spells it
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PromptMode {
+    #[default]
+    Word,
+    Dictation,
+    Picture,
+    Translate,
+}
+
+impl PromptMode {
+    pub const ALL: [PromptMode; 4] = [
+        PromptMode::Word,
+        PromptMode::Dictation,
+        PromptMode::Picture,
+        PromptMode::Translate,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PromptMode::Word => "Show the word",
+            PromptMode::Dictation => "Listen and spell",
+            PromptMode::Picture => "Show a picture",
+            PromptMode::Translate => "Translate and spell",
+        }
+    }
+
+    pub fn from_label(label: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.label() == label)
+    }
+
+    pub fn shows_word(&self) -> bool {
+        matches!(self, PromptMode::Word)
+    }
+
+    // Stored with results and leaderboard scores
+    pub fn code(&self) -> &'static str {
+        match self {
+            PromptMode::Word => "word",
+            PromptMode::Dictation => "dictation",
+            PromptMode::Picture => "picture",
+            PromptMode::Translate => "translate",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.code() == code)
+    }
+}
+
+// Defaults fill in fields missing from settings saved by an older version
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameSettings {
+    // Show Arabic letters in the form they take in the target word instead of isolated
+    pub contextual_forms: bool,
+    pub difficulty: Difficulty,
+    pub letter_case: LetterCase,
+    pub speech: SpeechSettings,
+    pub letter_speech: LetterSpeech,
+    // After a correct spelling, ask the learner to say the word aloud
+    pub say_the_word: bool,
+    pub prompt: PromptMode,
+    // Language code for glosses shown under the word, None hides them
+    pub translation_language: Option<String>,
+    // Prefer words with the learner's weakest letters
+    pub weak_letters: bool,
+    // Words to spell correctly each day, 0 for no goal
+    pub daily_goal: u32,
+    // Email a signed-in learner in the evening when they have not practised enough
+    pub reminders: bool,
+}
+
+impl GameSettings {
+    // Translate-and-spell needs a gloss even when glosses are switched off
+    pub fn translation_language(&self) -> Option<&str> {
+        self.translation_language
+            .as_deref()
+            .or((self.prompt == PromptMode::Translate).then_some(DEFAULT_TRANSLATION_LANGUAGE))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameState {
     pub current_word: Word,
     pub user_input: UserInput,
     pub score: i32,
+    pub words_correct: u32,
     pub attempts: i32,
     pub max_attempts: i32,
     pub current_attempt: i32,
     pub is_completed: bool,
+    // Holds the next word back until the learner has tried saying this one
+    pub awaiting_pronunciation: bool,
     pub language: Language,
     pub language_letters: Vec<String>,
+    // Alphabet rows keyed by letter, used for per-letter pronunciation
+    pub letter_details: HashMap<String, Letter>,
     pub game_letters: Vec<String>,
     pub game_grid_size: usize,
+    pub tile_mode: TileMode,
 }
 
 impl GameState {
@@ -77,19 +269,22 @@ impl GameState {
             current_word: Word::new(),
             user_input: UserInput::new(),
             score: 0,
+            words_correct: 0,
             attempts: 0,
             max_attempts: 5,
             current_attempt: 1,
             is_completed: false,
+            awaiting_pronunciation: false,
             language_letters: vec![],
+            letter_details: HashMap::new(),
             game_letters: vec![],
             game_grid_size: GAME_GRID_SIZE,
+            tile_mode: Difficulty::default().tile_mode(),
         }
     }
 
     pub fn add_letter(&mut self, letter: &str) {
-        leptos::logging::log!("original current_word: {}", self.current_word.word);
-        if self.user_input.len() < self.current_word.len() {
+        if self.user_input.len() < self.current_word.len(self.tile_mode) {
             self.user_input.add_letter(letter);
         }
     }
@@ -100,17 +295,20 @@ impl GameState {
 
     pub fn check_spelling(&mut self) -> bool {
         self.attempts += 1;
-        let is_correct = self.user_input == self.current_word;
+        let is_correct = self
+            .user_input
+            .matches(&self.current_word, &self.language.code);
 
         if is_correct {
             // Calculate points: 1 point per character + bonus for first try
-            let base_points = self.current_word.len() as i32;
+            let base_points = self.current_word.len(self.tile_mode) as i32;
             let bonus_points = if self.attempts == 1 {
                 10
             } else {
                 (10 - (self.attempts - 1) * 2).max(0)
             };
             self.score += base_points + bonus_points;
+            self.words_correct += 1;
             self.is_completed = true;
         } else if self.attempts >= self.max_attempts {
             self.is_completed = true;
@@ -119,10 +317,22 @@ impl GameState {
         is_correct
     }
 
+    pub fn record_pronunciation(&mut self, is_correct: bool) {
+        if is_correct {
+            self.score += PRONUNCIATION_BONUS;
+        }
+        self.awaiting_pronunciation = false;
+    }
+
     pub fn reset_for_next_word(&mut self, next_word: Word) {
-        let alphabet_letters = self.language_letters.clone();
+        let alphabet_letters =
+            if self.tile_mode == TileMode::Syllables && korean::is_korean_word(&next_word.word) {
+                korean::syllables_from_jamo(&self.language_letters)
+            } else {
+                self.language_letters.clone()
+            };
         let grid_size = self.game_grid_size;
-        let mut grid_letters = next_word.letters_for_grid();
+        let mut grid_letters = next_word.letters_for_grid(self.tile_mode);
         let mut rng = rand::rng();
         let mut distractor_letters: Vec<String> = alphabet_letters
             .into_iter()
@@ -135,26 +345,79 @@ impl GameState {
         final_grid.shuffle(&mut rng);
 
         self.current_word = next_word;
-        self.user_input = UserInput::new();
+        self.user_input = UserInput::with_mode(self.tile_mode);
         self.attempts = 0;
         self.current_attempt = 1;
         self.is_completed = false;
+        self.awaiting_pronunciation = false;
         self.game_letters = final_grid;
     }
 
-    pub fn set_language_letters(&mut self, letters: Vec<String>) {
-        self.language_letters = letters;
+    pub fn set_language_letters(&mut self, letters: Vec<Letter>) {
+        self.language_letters = letters.iter().map(|l| l.letter.clone()).collect();
+        self.letter_details = letters.into_iter().map(|l| (l.letter.clone(), l)).collect();
     }
 
     pub fn set_game_letters(&mut self, letters: Vec<String>) {
         self.game_letters = letters;
     }
+
+    // Pair each grid letter with the label shown on its tile
+    pub fn grid_labels(&self, settings: &GameSettings) -> Vec<(String, String)> {
+        let forms = if settings.contextual_forms {
+            self.current_word.contextual_forms()
+        } else {
+            vec![]
+        };
+        self.game_letters
+            .iter()
+            .map(|letter| {
+                // A letter used more than once shows the form of its next position to spell
+                let positions: Vec<&String> = forms
+                    .iter()
+                    .filter(|(l, _)| l == letter)
+                    .map(|(_, form)| form)
+                    .collect();
+                let typed = self
+                    .user_input
+                    .parts
+                    .iter()
+                    .filter(|p| *p == letter)
+                    .count();
+                let label = match positions.get(typed).or(positions.last()) {
+                    Some(form) => form.to_string(),
+                    // A bare space tile would look empty
+                    None if letter == " " => SPACE_LABEL.to_string(),
+                    None => indic::tile_label(letter),
+                };
+                let label = settings.letter_case.apply(&label, &self.language.code);
+                (letter.clone(), label)
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct GameContext {
     pub state: RwSignal<GameState>,
     pub current_language: RwSignal<Language>,
+    pub settings: RwSignal<GameSettings>,
+    // Set when the browser cannot speak the current language so the UI can show a fallback
+    pub speech_status: RwSignal<Option<SpeechError>>,
+    // Gloss of the current word in the chosen translation language
+    pub translation: RwSignal<Option<String>>,
+    // Learner whose results are recorded, None for a guest
+    pub profile_id: Option<String>,
+    // Name shown on leaderboards
+    pub player_name: Option<String>,
+    // Server-side game this context mirrors
+    pub session: RwSignal<Option<SessionState>>,
+    // Score and correct words from before the current session, which the session's own
+    // totals are added to
+    pub session_base: RwSignal<(i32, u32)>,
+    // Achievements waiting to be shown as toasts
+    pub new_achievements: RwSignal<Vec<Achievement>>,
+    pub streak: RwSignal<DailyStreak>,
 }
 
 impl GameContext {
@@ -162,6 +425,36 @@ impl GameContext {
         Self {
             state: RwSignal::new(GameState::new(language.clone())),
             current_language: RwSignal::new(language),
+            settings: RwSignal::new(GameSettings::default()),
+            speech_status: RwSignal::new(None),
+            translation: RwSignal::new(None),
+            profile_id: None,
+            player_name: None,
+            session: RwSignal::new(None),
+            session_base: RwSignal::new((0, 0)),
+            new_achievements: RwSignal::new(vec![]),
+            streak: RwSignal::new(DailyStreak::default()),
+        }
+    }
+
+    // Start from a learner's saved settings and score
+    pub fn for_profile(language: Language, profile: &Profile) -> Self {
+        let mut state = GameState::new(language.clone());
+        state.score = profile.score;
+        state.words_correct = profile.words_correct;
+        state.tile_mode = profile.settings.difficulty.tile_mode();
+        Self {
+            state: RwSignal::new(state),
+            current_language: RwSignal::new(language),
+            settings: RwSignal::new(profile.settings.clone()),
+            speech_status: RwSignal::new(None),
+            translation: RwSignal::new(None),
+            profile_id: Some(profile.id.clone()),
+            player_name: Some(profile.name.clone()),
+            session: RwSignal::new(None),
+            session_base: RwSignal::new((0, 0)),
+            new_achievements: RwSignal::new(vec![]),
+            streak: RwSignal::new(DailyStreak::default()),
         }
     }
 
@@ -177,12 +470,80 @@ impl GameContext {
         });
     }
 
-    pub fn check_spelling(&self) -> bool {
-        let mut result = false;
+    // The server checks the answer and keeps the score; the state here follows its reply
+    pub fn check_spelling(&self) {
+        let Some(session_id) = self.session_id() else {
+            return;
+        };
+        let answer = self.state.with_untracked(|state| state.user_input.clone());
+        let say_the_word = self.settings.with_untracked(|s| s.say_the_word);
+        let ctx = self.clone();
+        leptos::task::spawn_local(async move {
+            match session::submit_guess(session_id, ctx.profile_id.clone(), answer, say_the_word)
+                .await
+            {
+                Ok(session) => ctx.apply_session(session),
+                Err(e) => leptos::logging::log!("Error checking spelling: {:?}", e),
+            }
+        });
+    }
+
+    pub fn record_pronunciation(&self, is_correct: bool) {
+        let Some(session_id) = self.session_id() else {
+            return;
+        };
+        let ctx = self.clone();
+        leptos::task::spawn_local(async move {
+            match session::record_session_pronunciation(
+                session_id,
+                ctx.profile_id.clone(),
+                is_correct,
+            )
+            .await
+            {
+                Ok(session) => ctx.apply_session(session),
+                Err(e) => leptos::logging::log!("Error recording pronunciation: {:?}", e),
+            }
+        });
+    }
+
+    fn session_id(&self) -> Option<String> {
+        self.session
+            .with_untracked(|session| session.as_ref().map(|session| session.id.clone()))
+    }
+
+    // Mirror the server's game, dealing its word when it has moved on to a new one
+    pub fn apply_session(&self, session: SessionState) {
+        let previous = self.session.get_untracked();
+        if previous.as_ref().is_none_or(|p| p.id != session.id) {
+            let base = self
+                .state
+                .with_untracked(|state| (state.score, state.words_correct));
+            self.session_base.set(base);
+        }
+        let new_word = previous
+            .as_ref()
+            .is_none_or(|p| p.id != session.id || p.round != session.round);
+        let (base_score, base_words_correct) = self.session_base.get_untracked();
         self.state.update(|state| {
-            result = state.check_spelling();
+            if new_word {
+                state.tile_mode = session.tile_mode;
+                state.reset_for_next_word(session.word.clone());
+            }
+            state.attempts = session.attempts;
+            state.is_completed = session.is_completed;
+            state.awaiting_pronunciation = session.awaiting_pronunciation;
+            state.score = base_score + session.score;
+            state.words_correct = base_words_correct + session.words_correct;
         });
-        result
+        if let Some(streak) = session.streak {
+            self.streak.set(streak);
+        }
+        if !session.new_achievements.is_empty() {
+            self.new_achievements
+                .update(|toasts| toasts.extend(&session.new_achievements));
+        }
+        self.session.set(Some(session));
     }
 
     pub fn set_language(&self, language: &Language) {
@@ -198,12 +559,72 @@ impl GameContext {
         });
     }
 
-    pub fn set_language_letters(&self, letters: Vec<String>) {
+    pub fn set_language_letters(&self, letters: Vec<Letter>) {
         self.state.update(|state| {
             state.set_language_letters(letters);
         });
     }
 
+    // A server game keeps the tile mode it started with, so a change of tile mode takes
+    // effect with the next word, which starts a new game
+    pub fn set_difficulty(&self, difficulty: Difficulty) {
+        self.settings
+            .update(|settings| settings.difficulty = difficulty);
+        if self.session.with_untracked(Option::is_none) {
+            self.state.update(|state| {
+                state.tile_mode = difficulty.tile_mode();
+                let current_word = state.current_word.clone();
+                state.reset_for_next_word(current_word);
+            });
+        }
+    }
+
+    pub fn speak(&self, text: &str) {
+        self.pronounce(text, None);
+    }
+
+    // Prefer a recorded pronunciation, otherwise use speech synthesis
+    pub fn pronounce(&self, text: &str, audio_url: Option<&str>) {
+        let language = self.current_language.get_untracked();
+        let settings = self.settings.get_untracked();
+        let result = speech::pronounce(text, audio_url, language.locale(), &settings.speech);
+        self.speech_status.set(result.err());
+    }
+
+    // Recordings are of the letter's name, so sound mode always uses speech synthesis
+    pub fn pronounce_letter(&self, letter: &str) {
+        let letter_speech = self.settings.with_untracked(|s| s.letter_speech);
+        let details = self
+            .state
+            .with_untracked(|state| state.letter_details.get(letter).cloned());
+        let Some(details) = details else {
+            self.speak(letter);
+            return;
+        };
+        match letter_speech {
+            LetterSpeech::Name => self.pronounce(
+                details.spoken_name.as_deref().unwrap_or(letter),
+                details.audio_url.as_deref(),
+            ),
+            LetterSpeech::Sound => self.speak(details.phoneme.as_deref().unwrap_or(letter)),
+        }
+    }
+
+    pub fn pronounce_current_word(&self) {
+        let word = self
+            .state
+            .with_untracked(|state| state.current_word.clone());
+        if !word.word.is_empty() {
+            self.pronounce(&word.word, word.audio_url.as_deref());
+        }
+    }
+
+    pub fn refresh_speech_status(&self) {
+        let language = self.current_language.get_untracked();
+        self.speech_status
+            .set(speech::voice_status(language.locale()).err());
+    }
+
     pub fn get_current_word(&self) -> String {
         self.state.get().current_word.word.clone()
     }
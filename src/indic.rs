@@ -0,0 +1,108 @@
+// Brahmic scripts from Devanagari to Malayalam share a layout where each block is 128 code
+// points and the virama, nukta and vowel signs sit at the same offsets in every block.
+// Sinhala follows at U+0D80 but not the layout (its virama is U+0DCA), so it is left out.
+const INDIC_START: u32 = 0x0900;
+const INDIC_END: u32 = 0x0D7F;
+const BLOCK_SIZE: u32 = 0x80;
+
+const DOTTED_CIRCLE: char = '\u{25CC}';
+const ZERO_WIDTH_JOINER: char = '\u{200D}';
+const ZERO_WIDTH_NON_JOINER: char = '\u{200C}';
+
+fn block_offset(c: char) -> Option<u32> {
+    let code = c as u32;
+    (INDIC_START..=INDIC_END)
+        .contains(&code)
+        .then_some((code - INDIC_START) % BLOCK_SIZE)
+}
+
+pub fn is_indic(c: char) -> bool {
+    block_offset(c).is_some()
+}
+
+// Virama (halant) suppresses the inherent vowel and joins consonants into conjuncts
+pub fn is_virama(c: char) -> bool {
+    block_offset(c) == Some(0x4D)
+}
+
+pub fn is_nukta(c: char) -> bool {
+    block_offset(c) == Some(0x3C)
+}
+
+// Matras, virama and nasal marks attach to the preceding consonant
+pub fn is_dependent_sign(c: char) -> bool {
+    matches!(
+        block_offset(c),
+        Some(0x00..=0x03 | 0x3A..=0x3C | 0x3E..=0x4F | 0x51..=0x57 | 0x62..=0x63)
+    )
+}
+
+// Split a word into tiles: consonants, independent vowels, matras and viramas each
+// get their own tile, while a nukta or joiner stays with the tile before it
+pub fn segment(word: &str) -> Vec<String> {
+    let mut tiles: Vec<String> = vec![];
+    for c in word.chars() {
+        let attaches = is_nukta(c) || c == ZERO_WIDTH_JOINER || c == ZERO_WIDTH_NON_JOINER;
+        match tiles.last_mut() {
+            Some(last) if attaches => last.push(c),
+            _ => tiles.push(c.to_string()),
+        }
+    }
+    tiles
+}
+
+// Show dependent signs on a dotted circle so a lone matra or virama is visible on a tile
+pub fn tile_label(tile: &str) -> String {
+    match tile.chars().next() {
+        Some(c) if is_dependent_sign(c) && !is_nukta(c) => format!("{DOTTED_CIRCLE}{tile}"),
+        _ => tile.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matras_get_their_own_tiles() {
+        assert_eq!(segment("किताब"), vec!["क", "ि", "त", "ा", "ब"]);
+    }
+
+    #[test]
+    fn conjuncts_split_at_the_virama() {
+        assert_eq!(segment("क्षमा"), vec!["क", "्", "ष", "म", "ा"]);
+        // Malayalam uses the same offsets
+        assert_eq!(segment("ക്ക"), vec!["ക", "്", "ക"]);
+    }
+
+    #[test]
+    fn nukta_and_joiners_stay_with_the_tile_before() {
+        assert_eq!(segment("ज़रा"), vec!["ज़", "र", "ा"]);
+        assert_eq!(segment("र्\u{200D}य"), vec!["र", "्\u{200D}", "य"]);
+    }
+
+    #[test]
+    fn signs_are_found_in_every_block() {
+        assert!(is_virama('\u{094D}'));
+        assert!(is_virama('\u{0BCD}'));
+        assert!(is_virama('\u{0D4D}'));
+        assert!(is_nukta('\u{093C}'));
+        assert!(is_dependent_sign('\u{0BBE}'));
+        assert!(!is_dependent_sign('\u{0B95}'));
+    }
+
+    #[test]
+    fn sinhala_is_not_treated_as_indic() {
+        assert!(!is_indic('\u{0D85}'));
+        assert!(!is_virama('\u{0DCA}'));
+        assert_eq!(tile_label("\u{0DCF}"), "\u{0DCF}");
+    }
+
+    #[test]
+    fn lone_signs_are_shown_on_a_dotted_circle() {
+        assert_eq!(tile_label("ि"), "\u{25CC}ि");
+        assert_eq!(tile_label("्"), "\u{25CC}्");
+        assert_eq!(tile_label("क"), "क");
+        assert_eq!(tile_label("ज़"), "ज़");
+    }
+}
@@ -0,0 +1,23 @@
+// Compatibility jamo ranges used by the Letters table
+const CONSONANTS: std::ops::RangeInclusive<char> = '\u{3131}'..='\u{314E}';
+const VOWELS: std::ops::RangeInclusive<char> = '\u{314F}'..='\u{3163}';
+
+pub fn is_korean_word(word: &str) -> bool {
+    word.chars().next().is_some_and(rustkorean::check_korean)
+}
+
+// Build open syllable blocks (initial consonant + vowel) from an alphabet of jamo,
+// used as distractor tiles when the grid shows whole syllables
+pub fn syllables_from_jamo(letters: &[String]) -> Vec<String> {
+    let jamo: Vec<char> = letters.iter().flat_map(|l| l.chars()).collect();
+    let consonants = jamo.iter().copied().filter(|c| CONSONANTS.contains(c));
+    let vowels: Vec<char> = jamo.iter().copied().filter(|c| VOWELS.contains(c)).collect();
+    consonants
+        .flat_map(|consonant| {
+            vowels
+                .iter()
+                .map(move |&vowel| rustkorean::compose_korean(vec![consonant, vowel]))
+        })
+        .filter(|syllable| syllable.chars().count() == 1)
+        .collect()
+}
@@ -0,0 +1,349 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::database::{Language, Letter, Word};
+use crate::validation::{self, Issue, LanguageReport};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PackFormat {
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackLanguage {
+    pub code: String,
+    pub name: String,
+    pub name_other: Option<String>,
+    #[serde(default)]
+    pub strip_diacritics: bool,
+    #[serde(default = "default_direction")]
+    pub direction: String,
+    pub speech_locale: Option<String>,
+}
+
+fn default_direction() -> String {
+    "ltr".to_string()
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackLetter {
+    pub letter: String,
+    pub regular: Option<bool>,
+    pub hidden: Option<bool>,
+    pub name_en: Option<String>,
+    pub audio_url: Option<String>,
+    pub spoken_name: Option<String>,
+    pub phoneme: Option<String>,
+    pub sort_order: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackWord {
+    pub word: String,
+    pub category: Option<String>,
+    pub audio_url: Option<String>,
+    pub image_url: Option<String>,
+    pub emoji: Option<String>,
+}
+
+// Everything needed to add a language: its metadata, alphabet and vocabulary
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LanguagePack {
+    pub language: PackLanguage,
+    pub letters: Vec<PackLetter>,
+    pub words: Vec<PackWord>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PackError {
+    Parse(String),
+    Invalid(LanguageReport),
+}
+
+impl fmt::Display for PackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PackError::Parse(reason) => write!(f, "Could not read language pack: {reason}"),
+            PackError::Invalid(report) => {
+                let issues: Vec<String> = report.issues.iter().map(ToString::to_string).collect();
+                write!(
+                    f,
+                    "Language pack for {} has {} problem(s): {}",
+                    report.language,
+                    report.issues.len(),
+                    issues.join("; ")
+                )
+            }
+        }
+    }
+}
+
+// CSV packs are one table: a single `language` row followed by `letter` and `word` rows.
+// `text` holds the language code, the letter or the word; unused columns are left empty.
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum RowKind {
+    Language,
+    Letter,
+    Word,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct PackRow {
+    kind: Option<RowKind>,
+    text: String,
+    name: Option<String>,
+    name_other: Option<String>,
+    strip_diacritics: Option<bool>,
+    direction: Option<String>,
+    speech_locale: Option<String>,
+    regular: Option<bool>,
+    hidden: Option<bool>,
+    spoken_name: Option<String>,
+    phoneme: Option<String>,
+    sort_order: Option<u32>,
+    category: Option<String>,
+    audio_url: Option<String>,
+    image_url: Option<String>,
+    emoji: Option<String>,
+}
+
+impl LanguagePack {
+    pub fn from_parts(language: &Language, letters: Vec<Letter>, words: Vec<Word>) -> Self {
+        Self {
+            language: PackLanguage {
+                code: language.code.clone(),
+                name: language.name.clone(),
+                name_other: language.name_other.clone(),
+                strip_diacritics: language.strip_diacritics,
+                direction: language.direction.clone(),
+                speech_locale: language.speech_locale.clone(),
+            },
+            letters: letters
+                .into_iter()
+                .map(|l| PackLetter {
+                    letter: l.letter,
+                    regular: l.regular,
+                    hidden: l.hidden,
+                    name_en: l.name_en,
+                    audio_url: l.audio_url,
+                    spoken_name: l.spoken_name,
+                    phoneme: l.phoneme,
+                    sort_order: l.sort_order,
+                })
+                .collect(),
+            words: words
+                .into_iter()
+                .map(|w| PackWord {
+                    word: w.word,
+                    category: w.category,
+                    audio_url: w.audio_url,
+                    image_url: w.image_url,
+                    emoji: w.emoji,
+                })
+                .collect(),
+        }
+    }
+
+    // Rows the game would load from D1 for this pack, used to run the same content checks
+    fn to_parts(&self) -> (Language, Vec<Letter>, Vec<Word>) {
+        let language = Language {
+            id: 0,
+            name: self.language.name.clone(),
+            name_other: self.language.name_other.clone(),
+            code: self.language.code.clone(),
+            strip_diacritics: self.language.strip_diacritics,
+            direction: self.language.direction.clone(),
+            speech_locale: self.language.speech_locale.clone(),
+        };
+        let letters = self
+            .letters
+            .iter()
+            .zip(1..)
+            .map(|(l, id)| Letter {
+                id,
+                letter: l.letter.clone(),
+                language_id: 0,
+                regular: l.regular,
+                hidden: l.hidden,
+                name_en: l.name_en.clone(),
+                audio_url: l.audio_url.clone(),
+                spoken_name: l.spoken_name.clone(),
+                phoneme: l.phoneme.clone(),
+                sort_order: l.sort_order,
+            })
+            .collect();
+        let words = self
+            .words
+            .iter()
+            .zip(1..)
+            .map(|(w, id)| Word {
+                id,
+                word: w.word.clone(),
+                language_id: 0,
+                audio_url: w.audio_url.clone(),
+                image_url: w.image_url.clone(),
+                emoji: w.emoji.clone(),
+                category: w.category.clone(),
+            })
+            .collect();
+        (language, letters, words)
+    }
+
+    // Check the pack against its own alphabet. A pack without words is allowed so an alphabet
+    // can be imported first; word ids in the report are 1-based positions in the pack.
+    pub fn validate(&self) -> Result<LanguageReport, PackError> {
+        if self.language.code.trim().is_empty() || self.language.name.trim().is_empty() {
+            return Err(PackError::Parse(
+                "language code and name are required".to_string(),
+            ));
+        }
+        if !matches!(self.language.direction.as_str(), "ltr" | "rtl") {
+            return Err(PackError::Parse(format!(
+                "direction must be ltr or rtl, not {}",
+                self.language.direction
+            )));
+        }
+        let (language, letters, words) = self.to_parts();
+        let report = validation::validate_language(&language, &letters, &words);
+        if report.issues.iter().any(|issue| issue != &Issue::NoWords) {
+            return Err(PackError::Invalid(report));
+        }
+        Ok(report)
+    }
+
+    #[cfg(feature = "ssr")]
+    pub fn parse(format: PackFormat, data: &str) -> Result<Self, PackError> {
+        match format {
+            PackFormat::Json => {
+                serde_json::from_str(data).map_err(|e| PackError::Parse(e.to_string()))
+            }
+            PackFormat::Csv => {
+                let rows = csv::Reader::from_reader(data.as_bytes())
+                    .deserialize()
+                    .collect::<Result<Vec<PackRow>, _>>()
+                    .map_err(|e| PackError::Parse(e.to_string()))?;
+                Self::from_rows(rows)
+            }
+        }
+    }
+
+    #[cfg(feature = "ssr")]
+    pub fn write(&self, format: PackFormat) -> Result<String, PackError> {
+        match format {
+            PackFormat::Json => {
+                serde_json::to_string_pretty(self).map_err(|e| PackError::Parse(e.to_string()))
+            }
+            PackFormat::Csv => {
+                let mut writer = csv::Writer::from_writer(vec![]);
+                for row in self.to_rows() {
+                    writer
+                        .serialize(row)
+                        .map_err(|e| PackError::Parse(e.to_string()))?;
+                }
+                let bytes = writer
+                    .into_inner()
+                    .map_err(|e| PackError::Parse(e.to_string()))?;
+                String::from_utf8(bytes).map_err(|e| PackError::Parse(e.to_string()))
+            }
+        }
+    }
+
+    #[cfg(feature = "ssr")]
+    fn from_rows(rows: Vec<PackRow>) -> Result<Self, PackError> {
+        let mut language = None;
+        let mut letters = Vec::new();
+        let mut words = Vec::new();
+        for (line, row) in rows.into_iter().enumerate() {
+            match row.kind {
+                Some(RowKind::Language) if language.is_some() => {
+                    return Err(PackError::Parse(format!(
+                        "row {}: only one language row is allowed",
+                        line + 1
+                    )));
+                }
+                Some(RowKind::Language) => {
+                    language = Some(PackLanguage {
+                        code: row.text,
+                        name: row.name.unwrap_or_default(),
+                        name_other: row.name_other,
+                        strip_diacritics: row.strip_diacritics.unwrap_or_default(),
+                        direction: row.direction.unwrap_or_else(default_direction),
+                        speech_locale: row.speech_locale,
+                    })
+                }
+                Some(RowKind::Letter) => letters.push(PackLetter {
+                    letter: row.text,
+                    regular: row.regular,
+                    hidden: row.hidden,
+                    name_en: row.name,
+                    audio_url: row.audio_url,
+                    spoken_name: row.spoken_name,
+                    phoneme: row.phoneme,
+                    sort_order: row.sort_order,
+                }),
+                Some(RowKind::Word) => words.push(PackWord {
+                    word: row.text,
+                    category: row.category,
+                    audio_url: row.audio_url,
+                    image_url: row.image_url,
+                    emoji: row.emoji,
+                }),
+                None => {
+                    return Err(PackError::Parse(format!("row {}: missing kind", line + 1)));
+                }
+            }
+        }
+        let language =
+            language.ok_or_else(|| PackError::Parse("missing language row".to_string()))?;
+        Ok(Self {
+            language,
+            letters,
+            words,
+        })
+    }
+
+    #[cfg(feature = "ssr")]
+    fn to_rows(&self) -> Vec<PackRow> {
+        let language = PackRow {
+            kind: Some(RowKind::Language),
+            text: self.language.code.clone(),
+            name: Some(self.language.name.clone()),
+            name_other: self.language.name_other.clone(),
+            strip_diacritics: Some(self.language.strip_diacritics),
+            direction: Some(self.language.direction.clone()),
+            speech_locale: self.language.speech_locale.clone(),
+            ..PackRow::default()
+        };
+        let letters = self.letters.iter().map(|l| PackRow {
+            kind: Some(RowKind::Letter),
+            text: l.letter.clone(),
+            name: l.name_en.clone(),
+            regular: l.regular,
+            hidden: l.hidden,
+            spoken_name: l.spoken_name.clone(),
+            phoneme: l.phoneme.clone(),
+            sort_order: l.sort_order,
+            audio_url: l.audio_url.clone(),
+            ..PackRow::default()
+        });
+        let words = self.words.iter().map(|w| PackRow {
+            kind: Some(RowKind::Word),
+            text: w.word.clone(),
+            category: w.category.clone(),
+            audio_url: w.audio_url.clone(),
+            image_url: w.image_url.clone(),
+            emoji: w.emoji.clone(),
+            ..PackRow::default()
+        });
+        std::iter::once(language)
+            .chain(letters)
+            .chain(words)
+            .collect()
+    }
+}
@@ -0,0 +1,156 @@
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ssr")]
+use crate::auth::User;
+use crate::game::PromptMode;
+#[cfg(feature = "ssr")]
+use crate::progress::Player;
+
+pub const LEADERBOARD_SIZE: u32 = 10;
+#[cfg(feature = "ssr")]
+const MAX_NAME_LEN: usize = 20;
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Period {
+    #[default]
+    Weekly,
+    AllTime,
+}
+
+impl Period {
+    pub const ALL: [Period; 2] = [Period::Weekly, Period::AllTime];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Period::Weekly => "This week",
+            Period::AllTime => "All time",
+        }
+    }
+
+    pub fn from_label(label: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.label() == label)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub rank: u32,
+    pub name: String,
+    pub score: i64,
+}
+
+// Name shown on the leaderboard: the learner's name, else the account's, else a placeholder
+#[cfg(feature = "ssr")]
+pub fn player_name(name: Option<&str>, user: Option<&User>) -> String {
+    match (name.map(str::trim), user) {
+        (Some(name), _) if !name.is_empty() => name.chars().take(MAX_NAME_LEN).collect(),
+        (_, Some(user)) => user
+            .display_name
+            .clone()
+            .unwrap_or_else(|| user.email.split('@').next().unwrap_or_default().to_string()),
+        _ => "Player".to_string(),
+    }
+}
+
+// Post a game session's score. The score is added up from the word results recorded for the
+// session in its mode, rather than taken from the session, so it always matches them.
+#[cfg(feature = "ssr")]
+pub async fn submit_score(
+    conn: &sqlx_d1::D1Connection,
+    player: &Player,
+    session_id: &str,
+    name: &str,
+    language_id: u32,
+    mode: PromptMode,
+) -> Result<(), ServerFnError> {
+    // Players with neither a profile nor an account have nothing to rank
+    if player.is_guest() {
+        return Ok(());
+    }
+    let mode = mode.code();
+    let score = sqlx_d1::query!(
+        "SELECT COALESCE(SUM(score), 0) AS score FROM GameResults WHERE session_id = ? AND mode = ? AND language_id = ?",
+        session_id,
+        mode,
+        language_id
+    )
+    .fetch_one(conn)
+    .await
+    .map_err(|e| worker::Error::RustError(e.to_string()))?
+    .score;
+    if score <= 0 {
+        return Ok(());
+    }
+
+    sqlx_d1::query!(
+        "INSERT INTO Leaderboard (session_id, user_id, profile_id, name, language_id, mode, score) VALUES (?, ?, ?, ?, ?, ?, ?) ON CONFLICT(session_id) DO UPDATE SET score = excluded.score WHERE excluded.score > Leaderboard.score",
+        session_id,
+        player.user_id,
+        player.profile_id,
+        name,
+        language_id,
+        mode,
+        score
+    )
+    .execute(conn)
+    .await
+    .map_err(|e| worker::Error::RustError(e.to_string()))?;
+
+    Ok(())
+}
+
+// Top players by their best session, one row per profile or account
+#[server]
+pub async fn get_leaderboard(
+    language_id: u32,
+    mode: PromptMode,
+    period: Period,
+) -> Result<Vec<LeaderboardEntry>, ServerFnError> {
+    use axum::Extension;
+    use std::sync::Arc;
+    use worker::Env;
+
+    let mode = mode.code();
+    let Extension::<Arc<Env>>(env) = leptos_axum::extract().await?;
+    let d1 = env.d1("alphabet_game_stg")?;
+    let conn = sqlx_d1::D1Connection::new(d1);
+
+    let rows: Vec<(String, Option<i64>)> = match period {
+        // Weeks start on Monday
+        Period::Weekly => sqlx_d1::query!(
+            "SELECT name, MAX(score) AS score FROM Leaderboard WHERE language_id = ? AND mode = ? AND created_at >= date('now', '-6 days', 'weekday 1') GROUP BY COALESCE(profile_id, 'user:' || user_id) ORDER BY score DESC LIMIT ?",
+            language_id,
+            mode,
+            LEADERBOARD_SIZE
+        )
+        .fetch_all(&conn)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?
+        .into_iter()
+        .map(|row| (row.name, row.score))
+        .collect(),
+        Period::AllTime => sqlx_d1::query!(
+            "SELECT name, MAX(score) AS score FROM Leaderboard WHERE language_id = ? AND mode = ? GROUP BY COALESCE(profile_id, 'user:' || user_id) ORDER BY score DESC LIMIT ?",
+            language_id,
+            mode,
+            LEADERBOARD_SIZE
+        )
+        .fetch_all(&conn)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?
+        .into_iter()
+        .map(|row| (row.name, row.score))
+        .collect(),
+    };
+
+    Ok(rows
+        .into_iter()
+        .zip(1..)
+        .map(|((name, score), rank)| LeaderboardEntry {
+            rank,
+            name,
+            score: score.unwrap_or_default(),
+        })
+        .collect())
+}
@@ -1,13 +1,32 @@
+// The nested view types of the game page are deeper than the default limit allows
+#![recursion_limit = "256"]
+
 #[cfg(feature = "ssr")]
 use worker::*;
 
 use crate::app::*;
 
+pub mod achievements;
 pub mod app;
+pub mod arabic;
+pub mod auth;
 mod components;
 pub mod database;
 pub mod game;
+pub mod indic;
+pub mod korean;
+pub mod language_pack;
+pub mod leaderboard;
+pub mod mastery;
+pub mod normalize;
+pub mod profile;
+pub mod progress;
+pub mod recognition;
+pub mod scheduler;
+pub mod session;
 pub mod speech;
+pub mod streak;
+pub mod validation;
 
 #[cfg(feature = "ssr")]
 pub fn register_server_functions() {
@@ -17,14 +36,42 @@ pub fn register_server_functions() {
     register_explicit::<database::GetLanguages>();
     register_explicit::<database::GetLettersForLanguage>();
     register_explicit::<database::GetWordsForLanguage>();
-    register_explicit::<database::GetRandomWordForLanguage>();
+    register_explicit::<database::GetNextWordForLanguage>();
+    register_explicit::<database::GetTranslation>();
+    register_explicit::<database::ValidateContent>();
+    register_explicit::<database::ImportLanguagePack>();
+    register_explicit::<database::ExportLanguagePack>();
+    register_explicit::<database::AdminLogin>();
+    register_explicit::<database::AdminLogout>();
+    register_explicit::<database::IsAdmin>();
+    register_explicit::<database::SaveLanguage>();
+    register_explicit::<database::DeleteLanguage>();
+    register_explicit::<database::SaveLetter>();
+    register_explicit::<database::DeleteLetter>();
+    register_explicit::<database::SaveWord>();
+    register_explicit::<database::DeleteWord>();
+    register_explicit::<auth::GetCurrentUser>();
+    register_explicit::<auth::RequestLoginCode>();
+    register_explicit::<auth::VerifyLoginCode>();
+    register_explicit::<auth::SignOut>();
+    register_explicit::<profile::SyncProfiles>();
+    register_explicit::<profile::DeleteSyncedProfile>();
+    register_explicit::<progress::GetProgress>();
+    register_explicit::<progress::GetLetterMastery>();
+    register_explicit::<leaderboard::GetLeaderboard>();
+    register_explicit::<achievements::GetAchievements>();
+    register_explicit::<session::StartGameSession>();
+    register_explicit::<session::SubmitGuess>();
+    register_explicit::<session::NextSessionWord>();
+    register_explicit::<session::RecordSessionPronunciation>();
+    register_explicit::<streak::SavePracticeReminder>();
 }
 
 #[cfg(feature = "ssr")]
 async fn router(env: Env) -> axum::Router {
     use std::sync::Arc;
 
-    use axum::{Extension, Router};
+    use axum::{middleware, Extension, Router};
     use leptos::prelude::*;
     use leptos_axum::{generate_route_list, LeptosRoutes};
 
@@ -40,6 +87,9 @@ async fn router(env: Env) -> axum::Router {
             move || shell(leptos_options.clone())
         })
         .with_state(leptos_options)
+        // Layers run bottom-up, so the Env extension is in place before the user is looked up
+        .layer(middleware::from_fn(auth::current_user_layer))
+        .layer(Extension(auth::email_sender(&env)))
         .layer(Extension(Arc::new(env))) // <- Allow leptos server functions to access Worker stuff
 }
 
@@ -55,6 +105,26 @@ async fn fetch(
     Ok(router(env).await.call(req).await?)
 }
 
+// Hourly cron trigger that sends practice reminders
+#[cfg(feature = "ssr")]
+#[event(scheduled)]
+async fn scheduled(_event: ScheduledEvent, env: Env, _ctx: ScheduleContext) {
+    let notifier = streak::reminder_notifier(&env);
+    let result = match env.d1("alphabet_game_stg") {
+        Ok(d1) => {
+            let conn = sqlx_d1::D1Connection::new(d1);
+            streak::send_due_reminders(&conn, notifier.as_ref())
+                .await
+                .map_err(|e| e.to_string())
+        }
+        Err(e) => Err(e.to_string()),
+    };
+    match result {
+        Ok(sent) => console_log!("Sent {sent} practice reminders"),
+        Err(e) => console_error!("Error sending practice reminders: {e}"),
+    }
+}
+
 #[cfg(feature = "hydrate")]
 #[wasm_bindgen::prelude::wasm_bindgen]
 pub fn hydrate() {
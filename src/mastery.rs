@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::database::{self, Letter, TileMode, Word};
+use crate::normalize;
+
+// Letters need a few attempts before they can count as weak
+const MIN_ATTEMPTS: u32 = 3;
+// Share of correct attempts from which a letter shows as mastered
+pub const MASTERED_SCORE: f32 = 0.8;
+pub const WEAK_LETTER_COUNT: usize = 5;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LetterMastery {
+    pub letter_id: u32,
+    pub correct: u32,
+    pub incorrect: u32,
+}
+
+impl LetterMastery {
+    pub fn attempts(&self) -> u32 {
+        self.correct + self.incorrect
+    }
+
+    // Share of attempts where the letter was right, None before it has been tried
+    pub fn score(&self) -> Option<f32> {
+        (self.attempts() > 0).then(|| self.correct as f32 / self.attempts() as f32)
+    }
+
+    pub fn is_mastered(&self) -> bool {
+        self.attempts() >= MIN_ATTEMPTS && self.score().is_some_and(|score| score >= MASTERED_SCORE)
+    }
+}
+
+// Compare an answer with the target letter by letter, ignoring case. Each letter of the target
+// is right when the answer has the same letter in the same place; separators are not letters.
+pub fn letter_feedback(answer: &str, target: &str, lang_code: &str) -> Vec<(String, bool)> {
+    let answer = database::decompose(answer.to_string(), TileMode::Letters);
+    database::decompose(target.to_string(), TileMode::Letters)
+        .into_iter()
+        .enumerate()
+        .filter(|(_, letter)| !database::is_separator(letter))
+        .map(|(index, letter)| {
+            let correct = answer.get(index).is_some_and(|given| {
+                normalize::normalize_answer(given, lang_code)
+                    == normalize::normalize_answer(&letter, lang_code)
+            });
+            (letter, correct)
+        })
+        .collect()
+}
+
+// Match feedback to alphabet rows; letters missing from the alphabet are left out
+pub fn feedback_letter_ids(
+    feedback: &[(String, bool)],
+    letters: &HashMap<String, Letter>,
+    lang_code: &str,
+) -> Vec<(u32, bool)> {
+    let by_text: HashMap<String, u32> = letters
+        .values()
+        .map(|letter| {
+            (
+                normalize::normalize_answer(&letter.letter, lang_code),
+                letter.id,
+            )
+        })
+        .collect();
+    feedback
+        .iter()
+        .filter_map(|(letter, correct)| {
+            by_text
+                .get(&normalize::normalize_answer(letter, lang_code))
+                .map(|id| (*id, *correct))
+        })
+        .collect()
+}
+
+// Letters with the lowest share of correct attempts, weakest first
+pub fn weakest_letters(mastery: &[LetterMastery], count: usize) -> Vec<u32> {
+    let mut tried: Vec<(&LetterMastery, f32)> = mastery
+        .iter()
+        .filter(|m| m.attempts() >= MIN_ATTEMPTS)
+        .filter_map(|m| m.score().map(|score| (m, score)))
+        .filter(|(_, score)| *score < 1.0)
+        .collect();
+    tried.sort_by(|(a, a_score), (b, b_score)| {
+        a_score
+            .total_cmp(b_score)
+            .then(b.incorrect.cmp(&a.incorrect))
+    });
+    tried
+        .into_iter()
+        .take(count)
+        .map(|(m, _)| m.letter_id)
+        .collect()
+}
+
+// Ids of the words that use any of the given letters
+pub fn words_with_letters(
+    words: &[Word],
+    letters: &[Letter],
+    letter_ids: &[u32],
+    lang_code: &str,
+) -> Vec<u32> {
+    let wanted: Vec<String> = letters
+        .iter()
+        .filter(|letter| letter_ids.contains(&letter.id))
+        .map(|letter| normalize::normalize_answer(&letter.letter, lang_code))
+        .collect();
+    words
+        .iter()
+        .filter(|word| {
+            database::decompose(word.word.clone(), TileMode::Letters)
+                .iter()
+                .any(|letter| wanted.contains(&normalize::normalize_answer(letter, lang_code)))
+        })
+        .map(|word| word.id)
+        .collect()
+}
@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+
+// Languages whose dotted and dotless i are separate letters
+fn has_dotless_i(lang_code: &str) -> bool {
+    matches!(lang_code, "tr" | "az")
+}
+
+pub fn nfc(text: &str) -> String {
+    text.nfc().collect()
+}
+
+// Lowercase with Turkish dotted/dotless i rules and ß folded to ss
+pub fn fold_case(text: &str, lang_code: &str) -> String {
+    let dotless_i = has_dotless_i(lang_code);
+    let mut folded = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            'I' if dotless_i => folded.push('ı'),
+            'İ' if dotless_i => folded.push('i'),
+            'ß' | 'ẞ' => folded.push_str("ss"),
+            _ => folded.extend(c.to_lowercase()),
+        }
+    }
+    folded
+}
+
+// Canonical form used to compare a spelled answer with the target word
+pub fn normalize_answer(text: &str, lang_code: &str) -> String {
+    nfc(&fold_case(&nfc(text), lang_code))
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LetterCase {
+    #[default]
+    AsWritten,
+    Lower,
+    Upper,
+}
+
+impl LetterCase {
+    pub const ALL: [LetterCase; 3] = [LetterCase::AsWritten, LetterCase::Lower, LetterCase::Upper];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LetterCase::AsWritten => "As written",
+            LetterCase::Lower => "lowercase",
+            LetterCase::Upper => "UPPERCASE",
+        }
+    }
+
+    pub fn from_label(label: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.label() == label)
+    }
+
+    // Change the case of a tile label, keeping one glyph per tile where possible
+    pub fn apply(&self, text: &str, lang_code: &str) -> String {
+        let dotless_i = has_dotless_i(lang_code);
+        match self {
+            LetterCase::AsWritten => text.to_string(),
+            LetterCase::Lower => text
+                .chars()
+                .flat_map(|c| match c {
+                    'I' if dotless_i => vec!['ı'],
+                    'İ' if dotless_i => vec!['i'],
+                    _ => c.to_lowercase().collect(),
+                })
+                .collect(),
+            LetterCase::Upper => text
+                .chars()
+                .flat_map(|c| match c {
+                    'i' if dotless_i => vec!['İ'],
+                    'ß' => vec!['ẞ'],
+                    _ => c.to_uppercase().collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turkish_keeps_dotted_and_dotless_i_apart() {
+        assert_eq!(fold_case("IŞIK", "tr"), "ışık");
+        assert_eq!(fold_case("İSTANBUL", "tr"), "istanbul");
+        assert_eq!(fold_case("Iİ", "az"), "ıi");
+    }
+
+    #[test]
+    fn other_languages_use_default_lowercasing() {
+        assert_eq!(fold_case("IŞIK", "en"), "işik");
+        // İ lowercases to i with a combining dot above
+        assert_eq!(fold_case("İ", "en"), "i\u{307}");
+    }
+
+    #[test]
+    fn sharp_s_folds_to_ss() {
+        assert_eq!(fold_case("Straße", "de"), "strasse");
+        assert_eq!(fold_case("STRAẞE", "de"), "strasse");
+        assert_eq!(fold_case("Straße", "de"), fold_case("STRASSE", "de"));
+    }
+
+    #[test]
+    fn answers_compare_regardless_of_encoding() {
+        assert_eq!(
+            normalize_answer("Cafe\u{301}", "fr"),
+            normalize_answer("café", "fr")
+        );
+    }
+
+    #[test]
+    fn letter_case_keeps_one_glyph_per_tile() {
+        assert_eq!(LetterCase::Upper.apply("i", "tr"), "İ");
+        assert_eq!(LetterCase::Upper.apply("i", "en"), "I");
+        assert_eq!(LetterCase::Upper.apply("ß", "de"), "ẞ");
+        assert_eq!(LetterCase::Lower.apply("I", "tr"), "ı");
+        assert_eq!(LetterCase::AsWritten.apply("Ab", "en"), "Ab");
+    }
+}
@@ -0,0 +1,260 @@
+use leptos::prelude::*;
+use leptos::server_fn::codec::Json;
+use serde::{Deserialize, Serialize};
+
+use crate::game::GameSettings;
+
+const STORAGE_KEY: &str = "learner_profiles";
+
+pub const AVATARS: [&str; 12] = [
+    "🦁", "🐼", "🦊", "🐸", "🐙", "🦄", "🐢", "🐝", "🦉", "🐬", "🐞", "🦖",
+];
+
+// A learner on a shared device, with their own settings, score and progress
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    pub id: String,
+    pub name: String,
+    pub avatar: String,
+    // Language code to start in, None uses the default language
+    pub language_code: Option<String>,
+    pub settings: GameSettings,
+    pub score: i32,
+    pub words_correct: u32,
+    // Milliseconds since the epoch, used to keep the newer copy when syncing
+    pub updated_at: i64,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            name: String::new(),
+            avatar: AVATARS[0].to_string(),
+            language_code: None,
+            settings: GameSettings::default(),
+            score: 0,
+            words_correct: 0,
+            updated_at: 0,
+        }
+    }
+}
+
+impl Profile {
+    pub fn new(name: String, avatar: String) -> Self {
+        Self {
+            id: random_id(),
+            name,
+            avatar,
+            updated_at: now(),
+            ..Self::default()
+        }
+    }
+
+    pub fn touch(&mut self) {
+        self.updated_at = now();
+    }
+}
+
+// Random hex id for records created in the browser
+pub fn random_id() -> String {
+    let mut bytes = [0u8; 8];
+    if let Err(e) = getrandom::fill(&mut bytes) {
+        leptos::logging::error!("Error generating id: {e}");
+    }
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn now() -> i64 {
+    web_sys::js_sys::Date::now() as i64
+}
+
+fn storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+// Profiles saved on this device; only available in the browser
+pub fn load_profiles() -> Vec<Profile> {
+    storage()
+        .and_then(|storage| storage.get_item(STORAGE_KEY).ok().flatten())
+        .and_then(|json| {
+            serde_json::from_str(&json)
+                .inspect_err(|e| leptos::logging::error!("Error reading profiles: {e}"))
+                .ok()
+        })
+        .unwrap_or_default()
+}
+
+pub fn save_profiles(profiles: &[Profile]) {
+    let Some(storage) = storage() else {
+        return;
+    };
+    match serde_json::to_string(profiles) {
+        Ok(json) => {
+            if let Err(e) = storage.set_item(STORAGE_KEY, &json) {
+                leptos::logging::error!("Error saving profiles: {e:?}");
+            }
+        }
+        Err(e) => leptos::logging::error!("Error saving profiles: {e}"),
+    }
+}
+
+// Keep the newer copy of each profile, by id
+pub fn merge_profiles(local: Vec<Profile>, remote: Vec<Profile>) -> Vec<Profile> {
+    let mut merged = local;
+    for profile in remote {
+        match merged.iter_mut().find(|p| p.id == profile.id) {
+            Some(existing) if existing.updated_at >= profile.updated_at => {}
+            Some(existing) => *existing = profile,
+            None => merged.push(profile),
+        }
+    }
+    merged
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ProfilesContext {
+    pub profiles: RwSignal<Vec<Profile>>,
+    // Id of the learner playing now, None for a guest
+    pub active: RwSignal<Option<String>>,
+    // Whether the learner picker is showing instead of the game
+    pub choosing: RwSignal<bool>,
+    // Profiles are read from localStorage after hydration
+    pub loaded: RwSignal<bool>,
+}
+
+impl ProfilesContext {
+    pub fn new() -> Self {
+        Self {
+            profiles: RwSignal::new(vec![]),
+            active: RwSignal::new(None),
+            choosing: RwSignal::new(false),
+            loaded: RwSignal::new(false),
+        }
+    }
+
+    // Ask who is playing at startup whenever this device has profiles
+    pub fn load(&self) {
+        let profiles = load_profiles();
+        self.choosing.set(!profiles.is_empty());
+        self.profiles.set(profiles);
+        self.loaded.set(true);
+    }
+
+    pub fn select(&self, id: Option<String>) {
+        self.active.set(id);
+        self.choosing.set(false);
+    }
+
+    // Re-read only when the learner changes, not on every save of their progress
+    pub fn active_profile(&self) -> Option<Profile> {
+        let active = self.active.get()?;
+        self.profiles
+            .with_untracked(|profiles| profiles.iter().find(|p| p.id == active).cloned())
+    }
+
+    pub fn add(&self, profile: Profile) {
+        self.profiles.update(|profiles| profiles.push(profile));
+        self.profiles
+            .with_untracked(|profiles| save_profiles(profiles));
+    }
+
+    pub fn remove(&self, id: &str) {
+        self.profiles
+            .update(|profiles| profiles.retain(|p| p.id != id));
+        self.profiles
+            .with_untracked(|profiles| save_profiles(profiles));
+    }
+
+    // Saves only when something changed so the sync timestamp stays meaningful
+    pub fn update(&self, id: &str, f: impl FnOnce(&mut Profile)) {
+        let Some(mut profile) = self
+            .profiles
+            .with_untracked(|profiles| profiles.iter().find(|p| p.id == id).cloned())
+        else {
+            return;
+        };
+        let before = profile.clone();
+        f(&mut profile);
+        if profile == before {
+            return;
+        }
+        profile.touch();
+        self.profiles.update(|profiles| {
+            if let Some(existing) = profiles.iter_mut().find(|p| p.id == id) {
+                *existing = profile;
+            }
+        });
+        self.profiles
+            .with_untracked(|profiles| save_profiles(profiles));
+    }
+
+    pub fn replace(&self, profiles: Vec<Profile>) {
+        save_profiles(&profiles);
+        self.profiles.set(profiles);
+    }
+}
+
+impl Default for ProfilesContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Upload this device's profiles to the signed-in account and return every profile it holds
+#[server(input = Json)]
+pub async fn sync_profiles(profiles: Vec<Profile>) -> Result<Vec<Profile>, ServerFnError> {
+    use axum::Extension;
+    use std::sync::Arc;
+    use worker::Env;
+
+    let user = crate::auth::require_user().await?;
+    let Extension::<Arc<Env>>(env) = leptos_axum::extract().await?;
+    let d1 = env.d1("alphabet_game_stg")?;
+    let conn = sqlx_d1::D1Connection::new(d1);
+
+    for profile in &profiles {
+        let data = serde_json::to_string(profile)?;
+        sqlx_d1::query!(
+            "INSERT INTO Profiles (id, user_id, data, updated_at) VALUES (?, ?, ?, ?) ON CONFLICT(user_id, id) DO UPDATE SET data = excluded.data, updated_at = excluded.updated_at WHERE excluded.updated_at > Profiles.updated_at",
+            profile.id,
+            user.id,
+            data,
+            profile.updated_at
+        )
+        .execute(&conn)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?;
+    }
+
+    let rows = sqlx_d1::query!("SELECT data FROM Profiles WHERE user_id = ?", user.id)
+        .fetch_all(&conn)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?;
+    rows.into_iter()
+        .map(|row| serde_json::from_str(&row.data).map_err(ServerFnError::from))
+        .collect()
+}
+
+#[server]
+pub async fn delete_synced_profile(id: String) -> Result<(), ServerFnError> {
+    use axum::Extension;
+    use std::sync::Arc;
+    use worker::Env;
+
+    let user = crate::auth::require_user().await?;
+    let Extension::<Arc<Env>>(env) = leptos_axum::extract().await?;
+    let d1 = env.d1("alphabet_game_stg")?;
+    let conn = sqlx_d1::D1Connection::new(d1);
+
+    sqlx_d1::query!(
+        "DELETE FROM Profiles WHERE user_id = ? AND id = ?",
+        user.id,
+        id
+    )
+    .execute(&conn)
+    .await
+    .map_err(|e| worker::Error::RustError(e.to_string()))?;
+    Ok(())
+}
@@ -0,0 +1,231 @@
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ssr")]
+use crate::game::PromptMode;
+use crate::mastery::LetterMastery;
+
+// A word as it was played, in the order it was played
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx_d1::FromRow)]
+pub struct GameResult {
+    pub language_id: u32,
+    pub language: String,
+    pub attempts: i32,
+    pub correct: bool,
+    pub score: i32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LanguageProgress {
+    pub language_id: u32,
+    pub language: String,
+    pub words: u32,
+    pub correct: u32,
+    pub attempts: u32,
+    pub score: i64,
+    // Words spelled correctly in a row, up to the latest one
+    pub current_streak: u32,
+    pub best_streak: u32,
+}
+
+impl LanguageProgress {
+    // Share of words spelled correctly, from 0 to 1
+    pub fn accuracy(&self) -> f32 {
+        if self.words == 0 {
+            0.0
+        } else {
+            self.correct as f32 / self.words as f32
+        }
+    }
+}
+
+// Totals per language, in the order each language was first played
+pub fn summarize(results: &[GameResult]) -> Vec<LanguageProgress> {
+    let mut progress: Vec<LanguageProgress> = Vec::new();
+    for result in results {
+        let index = match progress
+            .iter()
+            .position(|p| p.language_id == result.language_id)
+        {
+            Some(index) => index,
+            None => {
+                progress.push(LanguageProgress {
+                    language_id: result.language_id,
+                    language: result.language.clone(),
+                    ..LanguageProgress::default()
+                });
+                progress.len() - 1
+            }
+        };
+        let entry = &mut progress[index];
+        entry.words += 1;
+        entry.attempts += result.attempts.max(0) as u32;
+        entry.score += result.score as i64;
+        if result.correct {
+            entry.correct += 1;
+            entry.current_streak += 1;
+            entry.best_streak = entry.best_streak.max(entry.current_streak);
+        } else {
+            entry.current_streak = 0;
+        }
+    }
+    progress
+}
+
+// Whose results these are: a learner profile, a signed-in account, or both
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Player {
+    pub user_id: Option<u32>,
+    pub profile_id: Option<String>,
+}
+
+#[cfg(feature = "ssr")]
+impl Player {
+    // Guests without an account have nowhere to keep their history
+    pub fn is_guest(&self) -> bool {
+        self.user_id.is_none() && self.profile_id.is_none()
+    }
+
+    // One key per learner: the profile, or the account when playing without one
+    pub fn owner(&self) -> Option<String> {
+        self.profile_id
+            .clone()
+            .or_else(|| self.user_id.map(|id| format!("user:{id}")))
+    }
+}
+
+// A finished word as the game session saw it
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordResult {
+    pub word_id: u32,
+    pub language_id: u32,
+    pub attempts: i32,
+    pub correct: bool,
+    pub score: i32,
+    pub mode: PromptMode,
+    // Minutes east of UTC, so the word counts towards the learner's own day
+    pub utc_offset: i32,
+}
+
+// Progress for a learner profile, or for the signed-in account when no profile is given
+#[server]
+pub async fn get_progress(
+    profile_id: Option<String>,
+) -> Result<Vec<LanguageProgress>, ServerFnError> {
+    use axum::Extension;
+    use std::sync::Arc;
+    use worker::Env;
+
+    let Extension::<Arc<Env>>(env) = leptos_axum::extract().await?;
+    let d1 = env.d1("alphabet_game_stg")?;
+    let conn = sqlx_d1::D1Connection::new(d1);
+
+    let results = match profile_id {
+        Some(profile_id) => sqlx_d1::query_as!(
+            GameResult,
+            "SELECT r.language_id, l.name AS language, r.attempts, r.correct, r.score FROM GameResults r JOIN Languages l ON l.id = r.language_id WHERE r.profile_id = ? ORDER BY r.id",
+            profile_id
+        )
+        .fetch_all(&conn)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?,
+        None => {
+            let user = crate::auth::require_user().await?;
+            sqlx_d1::query_as!(
+                GameResult,
+                "SELECT r.language_id, l.name AS language, r.attempts, r.correct, r.score FROM GameResults r JOIN Languages l ON l.id = r.language_id WHERE r.user_id = ? ORDER BY r.id",
+                user.id
+            )
+            .fetch_all(&conn)
+            .await
+            .map_err(|e| worker::Error::RustError(e.to_string()))?
+        }
+    };
+
+    Ok(summarize(&results))
+}
+
+// Record which letters of the target word an answer got right
+#[cfg(feature = "ssr")]
+pub async fn record_letter_results(
+    conn: &sqlx_d1::D1Connection,
+    player: &Player,
+    language_id: u32,
+    results: &[(u32, bool)],
+) -> Result<(), ServerFnError> {
+    if player.is_guest() {
+        return Ok(());
+    }
+    for (letter_id, correct) in results {
+        sqlx_d1::query!(
+            "INSERT INTO LetterResults (user_id, profile_id, letter_id, language_id, correct) VALUES (?, ?, ?, ?, ?)",
+            player.user_id,
+            player.profile_id,
+            letter_id,
+            language_id,
+            correct
+        )
+        .execute(conn)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?;
+    }
+
+    Ok(())
+}
+
+// Right and wrong counts per letter for a learner profile or the signed-in account; empty for
+// a guest
+#[server]
+pub async fn get_letter_mastery(
+    language_id: u32,
+    profile_id: Option<String>,
+) -> Result<Vec<LetterMastery>, ServerFnError> {
+    use axum::Extension;
+    use std::sync::Arc;
+    use worker::Env;
+
+    let user_id = crate::auth::get_current_user().await?.map(|user| user.id);
+    let Extension::<Arc<Env>>(env) = leptos_axum::extract().await?;
+    let d1 = env.d1("alphabet_game_stg")?;
+    let conn = sqlx_d1::D1Connection::new(d1);
+
+    let counts: Vec<(i64, i64, Option<i64>)> = match (profile_id, user_id) {
+        (Some(profile_id), _) => sqlx_d1::query!(
+            "SELECT letter_id, COUNT(*) AS attempts, SUM(correct) AS correct FROM LetterResults WHERE language_id = ? AND profile_id = ? GROUP BY letter_id",
+            language_id,
+            profile_id
+        )
+        .fetch_all(&conn)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?
+        .into_iter()
+        .map(|row| (row.letter_id, row.attempts, row.correct))
+        .collect(),
+        (None, Some(user_id)) => sqlx_d1::query!(
+            "SELECT letter_id, COUNT(*) AS attempts, SUM(correct) AS correct FROM LetterResults WHERE language_id = ? AND user_id = ? GROUP BY letter_id",
+            language_id,
+            user_id
+        )
+        .fetch_all(&conn)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?
+        .into_iter()
+        .map(|row| (row.letter_id, row.attempts, row.correct))
+        .collect(),
+        (None, None) => vec![],
+    };
+
+    Ok(counts
+        .into_iter()
+        .map(|(letter_id, attempts, correct)| {
+            let correct = correct.unwrap_or_default();
+            LetterMastery {
+                letter_id: letter_id as u32,
+                correct: correct as u32,
+                incorrect: (attempts - correct) as u32,
+            }
+        })
+        .collect())
+}
@@ -0,0 +1,282 @@
+use std::{cell::RefCell, fmt, rc::Rc, sync::Arc};
+
+use leptos::prelude::*;
+use wasm_bindgen::{JsCast, JsValue, prelude::Closure};
+use web_sys::js_sys::{self, Reflect};
+
+use crate::normalize;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecognitionError {
+    Unsupported,
+    NoMatch,
+    Failed(String),
+}
+
+impl fmt::Display for RecognitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecognitionError::Unsupported => {
+                write!(f, "Speech recognition is not available in this browser")
+            }
+            RecognitionError::NoMatch => write!(f, "Didn't catch that"),
+            RecognitionError::Failed(reason) => write!(f, "Speech recognition failed: {reason}"),
+        }
+    }
+}
+
+pub type RecognitionCallback = Box<dyn FnOnce(Result<Vec<String>, RecognitionError>)>;
+
+// Listens for a single utterance and reports every transcript alternative it heard
+pub trait Recognizer: Send + Sync {
+    fn is_supported(&self) -> bool;
+    fn listen(&self, locale: &str, on_result: RecognitionCallback);
+}
+
+// Provided as context so the browser implementation can be swapped for a fake
+#[derive(Clone)]
+pub struct RecognizerContext(pub Arc<dyn Recognizer>);
+
+impl RecognizerContext {
+    pub fn browser() -> Self {
+        Self(Arc::new(BrowserRecognizer))
+    }
+}
+
+// Web Speech API recognizer; Chrome and Safari only expose the webkit-prefixed constructor
+pub struct BrowserRecognizer;
+
+impl BrowserRecognizer {
+    fn constructor() -> Option<js_sys::Function> {
+        let window = window();
+        ["SpeechRecognition", "webkitSpeechRecognition"]
+            .into_iter()
+            .filter_map(|name| Reflect::get(&window, &JsValue::from_str(name)).ok())
+            .find(|value| value.is_function())
+            .map(|value| value.unchecked_into())
+    }
+
+    fn start(
+        constructor: &js_sys::Function,
+        locale: &str,
+        on_result: RecognitionCallback,
+    ) -> Result<(), JsValue> {
+        let recognition = Reflect::construct(constructor, &js_sys::Array::new())?;
+        Reflect::set(&recognition, &"lang".into(), &locale.into())?;
+        Reflect::set(&recognition, &"interimResults".into(), &false.into())?;
+        Reflect::set(&recognition, &"maxAlternatives".into(), &5.into())?;
+
+        // Whichever event fires first answers the callback
+        let callback = Rc::new(RefCell::new(Some(on_result)));
+        let respond = move |result: Result<Vec<String>, RecognitionError>| {
+            if let Some(on_result) = callback.borrow_mut().take() {
+                on_result(result);
+            }
+        };
+
+        let on_result_event = Closure::<dyn Fn(JsValue)>::new({
+            let respond = respond.clone();
+            move |event: JsValue| respond(Ok(transcripts(&event)))
+        });
+        let on_error_event = Closure::<dyn Fn(JsValue)>::new({
+            let respond = respond.clone();
+            move |event: JsValue| {
+                let reason = Reflect::get(&event, &"error".into())
+                    .ok()
+                    .and_then(|error| error.as_string())
+                    .unwrap_or_default();
+                let error = match reason.as_str() {
+                    "no-speech" => RecognitionError::NoMatch,
+                    _ => RecognitionError::Failed(reason),
+                };
+                respond(Err(error));
+            }
+        });
+        let on_end_event =
+            Closure::<dyn Fn(JsValue)>::new(move |_| respond(Err(RecognitionError::NoMatch)));
+
+        Reflect::set(&recognition, &"onresult".into(), on_result_event.as_ref())?;
+        Reflect::set(&recognition, &"onerror".into(), on_error_event.as_ref())?;
+        Reflect::set(&recognition, &"onend".into(), on_end_event.as_ref())?;
+        on_result_event.forget();
+        on_error_event.forget();
+        on_end_event.forget();
+
+        let start: js_sys::Function = Reflect::get(&recognition, &"start".into())?.dyn_into()?;
+        start.call0(&recognition)?;
+        Ok(())
+    }
+}
+
+impl Recognizer for BrowserRecognizer {
+    fn is_supported(&self) -> bool {
+        Self::constructor().is_some()
+    }
+
+    fn listen(&self, locale: &str, on_result: RecognitionCallback) {
+        let Some(constructor) = Self::constructor() else {
+            on_result(Err(RecognitionError::Unsupported));
+            return;
+        };
+        if let Err(e) = Self::start(&constructor, locale, on_result) {
+            leptos::logging::error!("Error starting speech recognition: {:?}", e);
+        }
+    }
+}
+
+// Collect the transcript of every alternative in the first result
+fn transcripts(event: &JsValue) -> Vec<String> {
+    let Ok(result) =
+        Reflect::get(event, &"results".into()).and_then(|results| Reflect::get_u32(&results, 0))
+    else {
+        return vec![];
+    };
+    let length = Reflect::get(&result, &"length".into())
+        .ok()
+        .and_then(|length| length.as_f64())
+        .unwrap_or_default() as u32;
+    (0..length)
+        .filter_map(|i| Reflect::get_u32(&result, i).ok())
+        .filter_map(|alternative| Reflect::get(&alternative, &"transcript".into()).ok())
+        .filter_map(|transcript| transcript.as_string())
+        .collect()
+}
+
+fn comparable(text: &str, lang_code: &str) -> String {
+    normalize::normalize_answer(text, lang_code)
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect()
+}
+
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+// Accept the word anywhere in any transcript, allowing one mistake per four letters since
+// recognizers often mishear short words or add articles
+pub fn is_lenient_match(transcripts: &[String], word: &str, lang_code: &str) -> bool {
+    let target: Vec<char> = comparable(word, lang_code)
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    if target.is_empty() {
+        return false;
+    }
+    let tolerance = (target.len() / 4).max(1);
+    transcripts.iter().any(|transcript| {
+        let transcript = comparable(transcript, lang_code);
+        let whole: Vec<char> = transcript.chars().filter(|c| !c.is_whitespace()).collect();
+        edit_distance(&whole, &target) <= tolerance
+            || transcript.split_whitespace().any(|token| {
+                let token: Vec<char> = token.chars().collect();
+                edit_distance(&token, &target) <= tolerance
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Answers every request with the same transcripts or error
+    struct FakeRecognizer(Result<Vec<String>, RecognitionError>);
+
+    impl FakeRecognizer {
+        fn heard(transcripts: &[&str]) -> Self {
+            Self(Ok(transcripts.iter().map(|t| t.to_string()).collect()))
+        }
+    }
+
+    impl Recognizer for FakeRecognizer {
+        fn is_supported(&self) -> bool {
+            self.0 != Err(RecognitionError::Unsupported)
+        }
+
+        fn listen(&self, _locale: &str, on_result: RecognitionCallback) {
+            on_result(self.0.clone());
+        }
+    }
+
+    fn listen(recognizer: &RecognizerContext) -> Result<Vec<String>, RecognitionError> {
+        let heard = Rc::new(RefCell::new(None));
+        recognizer.0.listen("en-US", {
+            let heard = heard.clone();
+            Box::new(move |result| *heard.borrow_mut() = Some(result))
+        });
+        heard.take().expect("the recognizer should answer")
+    }
+
+    fn strings(transcripts: &[&str]) -> Vec<String> {
+        transcripts.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn edit_distance_counts_single_character_edits() {
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+        assert_eq!(edit_distance(&chars("cat"), &chars("cat")), 0);
+        assert_eq!(edit_distance(&chars("cat"), &chars("bat")), 1);
+        assert_eq!(edit_distance(&chars("cat"), &chars("cast")), 1);
+        assert_eq!(edit_distance(&chars("cat"), &chars("at")), 1);
+        assert_eq!(edit_distance(&chars("kitten"), &chars("sitting")), 3);
+        assert_eq!(edit_distance(&chars(""), &chars("dog")), 3);
+    }
+
+    #[test]
+    fn short_words_allow_one_mistake() {
+        // max(1, 3 / 4) = 1
+        assert!(is_lenient_match(&strings(&["bat"]), "cat", "en"));
+        assert!(!is_lenient_match(&strings(&["bag"]), "cat", "en"));
+        assert!(is_lenient_match(&strings(&["ax"]), "ox", "en"));
+    }
+
+    #[test]
+    fn longer_words_allow_one_mistake_per_four_letters() {
+        // max(1, 8 / 4) = 2
+        assert!(is_lenient_match(&strings(&["elefant"]), "elephant", "en"));
+        assert!(!is_lenient_match(&strings(&["elefan"]), "elephant", "en"));
+    }
+
+    #[test]
+    fn the_word_may_be_anywhere_in_any_alternative() {
+        let transcripts = strings(&["hat", "the cat sat"]);
+        assert!(is_lenient_match(&transcripts, "cat", "en"));
+        assert!(is_lenient_match(&strings(&["ice cream"]), "icecream", "en"));
+        assert!(is_lenient_match(
+            &strings(&["Ice-cream!"]),
+            "ice cream",
+            "en"
+        ));
+        assert!(!is_lenient_match(&[], "cat", "en"));
+        assert!(!is_lenient_match(&strings(&["cat"]), "", "en"));
+    }
+
+    #[test]
+    fn matching_ignores_case_and_encoding() {
+        assert!(is_lenient_match(&strings(&["CAFÉ"]), "cafe\u{301}", "fr"));
+        assert!(is_lenient_match(&strings(&["IŞIK"]), "ışık", "tr"));
+    }
+
+    #[test]
+    fn a_fake_recognizer_can_stand_in_for_the_browser() {
+        let recognizer = RecognizerContext(Arc::new(FakeRecognizer::heard(&["a dog", "the dog"])));
+        assert!(recognizer.0.is_supported());
+        let transcripts = listen(&recognizer).unwrap();
+        assert!(is_lenient_match(&transcripts, "dog", "en"));
+        assert!(!is_lenient_match(&transcripts, "horse", "en"));
+
+        let unsupported =
+            RecognizerContext(Arc::new(FakeRecognizer(Err(RecognitionError::Unsupported))));
+        assert!(!unsupported.0.is_supported());
+        assert_eq!(listen(&unsupported), Err(RecognitionError::Unsupported));
+    }
+}
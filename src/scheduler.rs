@@ -0,0 +1,247 @@
+// SM-2 style spaced repetition over a learner's word results. Pure functions of the results
+// and the current time, so scheduling can be checked without D1.
+use std::collections::HashMap;
+
+use rand::Rng;
+use rand::seq::IndexedRandom;
+use serde::{Deserialize, Serialize};
+
+const DAY: i64 = 24 * 60 * 60;
+// A missed word comes back within the same session
+const RELEARN_DELAY: i64 = 10 * 60;
+const START_EASE: f32 = 2.5;
+const MIN_EASE: f32 = 1.3;
+
+// One finished word; `at` is in seconds since the epoch
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Review {
+    pub word_id: u32,
+    pub correct: bool,
+    pub attempts: i32,
+    pub at: i64,
+}
+
+impl Review {
+    // SM-2 grades recall from 0 to 5; spelling it first time is perfect recall
+    fn quality(&self) -> u8 {
+        match (self.correct, self.attempts) {
+            (false, _) => 1,
+            (true, ..=1) => 5,
+            (true, 2) => 4,
+            (true, _) => 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Card {
+    pub word_id: u32,
+    // Correct reviews in a row
+    pub repetitions: u32,
+    pub interval_days: f32,
+    pub ease: f32,
+    pub due: i64,
+}
+
+impl Card {
+    fn new(word_id: u32) -> Self {
+        Self {
+            word_id,
+            repetitions: 0,
+            interval_days: 0.0,
+            ease: START_EASE,
+            due: 0,
+        }
+    }
+
+    fn review(&mut self, review: &Review) {
+        let quality = review.quality();
+        if quality < 3 {
+            self.repetitions = 0;
+            self.interval_days = 0.0;
+            self.due = review.at + RELEARN_DELAY;
+            return;
+        }
+        self.repetitions += 1;
+        self.interval_days = match self.repetitions {
+            1 => 1.0,
+            2 => 6.0,
+            _ => self.interval_days * self.ease,
+        };
+        let lapse = f32::from(5 - quality);
+        self.ease = (self.ease + 0.1 - lapse * (0.08 + lapse * 0.02)).max(MIN_EASE);
+        self.due = review.at + (self.interval_days * DAY as f32) as i64;
+    }
+}
+
+// Replay results in the order they happened to get each word's current card
+pub fn schedule(reviews: &[Review]) -> HashMap<u32, Card> {
+    let mut cards = HashMap::new();
+    for review in reviews {
+        cards
+            .entry(review.word_id)
+            .or_insert_with(|| Card::new(review.word_id))
+            .review(review);
+    }
+    cards
+}
+
+// Most overdue review first, then a random word not seen yet, then the review due soonest.
+// `previous` is skipped while there is anything else to choose from, as its result may not
+// have been recorded yet.
+pub fn next_word<R: Rng + ?Sized>(
+    word_ids: &[u32],
+    cards: &HashMap<u32, Card>,
+    now: i64,
+    previous: Option<u32>,
+    rng: &mut R,
+) -> Option<u32> {
+    let candidates: Vec<u32> = word_ids
+        .iter()
+        .copied()
+        .filter(|id| Some(*id) != previous)
+        .collect();
+    if candidates.is_empty() {
+        return word_ids.first().copied();
+    }
+
+    let mut seen: Vec<&Card> = candidates.iter().filter_map(|id| cards.get(id)).collect();
+    seen.sort_by_key(|card| card.due);
+    if let Some(card) = seen.first().filter(|card| card.due <= now) {
+        return Some(card.word_id);
+    }
+
+    let new_words: Vec<u32> = candidates
+        .iter()
+        .copied()
+        .filter(|id| !cards.contains_key(id))
+        .collect();
+    new_words
+        .choose(rng)
+        .copied()
+        .or_else(|| seen.first().map(|card| card.word_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn review(word_id: u32, correct: bool, attempts: i32, at: i64) -> Review {
+        Review {
+            word_id,
+            correct,
+            attempts,
+            at,
+        }
+    }
+
+    #[test]
+    fn correct_reviews_lengthen_the_interval() {
+        let mut card = Card::new(1);
+        card.review(&review(1, true, 1, 0));
+        assert_eq!(
+            (card.repetitions, card.interval_days, card.due),
+            (1, 1.0, DAY)
+        );
+        card.review(&review(1, true, 1, DAY));
+        assert_eq!(
+            (card.repetitions, card.interval_days, card.due),
+            (2, 6.0, 7 * DAY)
+        );
+        // Each first-try answer makes the word a little easier
+        assert!((card.ease - (START_EASE + 0.2)).abs() < 1e-5);
+        let ease = card.ease;
+        card.review(&review(1, true, 1, 7 * DAY));
+        assert_eq!(card.repetitions, 3);
+        assert!((card.interval_days - 6.0 * ease).abs() < 1e-4);
+    }
+
+    #[test]
+    fn needing_more_attempts_lowers_the_ease() {
+        let mut card = Card::new(1);
+        card.review(&review(1, true, 2, 0));
+        assert!((card.ease - START_EASE).abs() < 1e-5);
+        card.review(&review(1, true, 3, DAY));
+        assert!(card.ease < START_EASE);
+        for day in 2..50 {
+            card.review(&review(1, true, 3, day * DAY));
+        }
+        assert_eq!(card.ease, MIN_EASE);
+    }
+
+    #[test]
+    fn a_lapse_brings_the_word_back_soon() {
+        let mut card = Card::new(1);
+        card.review(&review(1, true, 1, 0));
+        card.review(&review(1, true, 1, DAY));
+        let ease = card.ease;
+        card.review(&review(1, false, 4, 7 * DAY));
+        assert_eq!(card.repetitions, 0);
+        assert_eq!(card.interval_days, 0.0);
+        assert_eq!(card.due, 7 * DAY + RELEARN_DELAY);
+        assert_eq!(card.ease, ease);
+        // Relearning starts again from a one-day interval
+        card.review(&review(1, true, 1, 7 * DAY + RELEARN_DELAY));
+        assert_eq!(card.interval_days, 1.0);
+    }
+
+    #[test]
+    fn schedule_replays_results_per_word() {
+        let cards = schedule(&[
+            review(1, true, 1, 0),
+            review(2, false, 1, 0),
+            review(1, true, 1, DAY),
+        ]);
+        assert_eq!(cards[&1].repetitions, 2);
+        assert_eq!(cards[&2].due, RELEARN_DELAY);
+    }
+
+    #[test]
+    fn overdue_reviews_come_before_new_words() {
+        let cards = schedule(&[review(1, true, 1, 0), review(2, false, 1, 0)]);
+        let next = next_word(&[1, 2, 3], &cards, 2 * DAY, None, &mut rand::rng());
+        // Word 2 has been due longest
+        assert_eq!(next, Some(2));
+    }
+
+    #[test]
+    fn new_words_come_before_reviews_not_yet_due() {
+        let cards = schedule(&[review(1, true, 1, 0)]);
+        for _ in 0..10 {
+            let next = next_word(&[1, 2, 3], &cards, 0, None, &mut rand::rng());
+            assert!(matches!(next, Some(2 | 3)));
+        }
+    }
+
+    #[test]
+    fn previous_word_is_skipped() {
+        let cards = schedule(&[review(1, false, 1, 0)]);
+        let now = 2 * RELEARN_DELAY;
+        assert_eq!(
+            next_word(&[1, 2], &cards, now, None, &mut rand::rng()),
+            Some(1)
+        );
+        assert_eq!(
+            next_word(&[1, 2], &cards, now, Some(1), &mut rand::rng()),
+            Some(2)
+        );
+        // Unless it is the only word there is
+        assert_eq!(
+            next_word(&[1], &cards, now, Some(1), &mut rand::rng()),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn falls_back_to_the_review_due_soonest() {
+        let cards = schedule(&[
+            review(1, true, 1, 0),
+            review(2, true, 1, 0),
+            review(2, true, 1, DAY),
+            review(3, true, 1, 0),
+        ]);
+        let next = next_word(&[1, 2, 3], &cards, 0, Some(3), &mut rand::rng());
+        assert_eq!(next, Some(1));
+        assert_eq!(next_word(&[], &cards, 0, None, &mut rand::rng()), None);
+    }
+}
@@ -0,0 +1,583 @@
+// Games are played against the server: it deals each word, checks each answer with the same
+// `GameState` logic the browser used to run, and keeps the score. The browser only mirrors it.
+use leptos::prelude::*;
+use leptos::server_fn::codec::Json;
+use serde::{Deserialize, Serialize};
+
+use crate::achievements::Achievement;
+#[cfg(feature = "ssr")]
+use crate::database::Language;
+use crate::database::{TileMode, Word};
+#[cfg(feature = "ssr")]
+use crate::game::GameState;
+use crate::game::{PromptMode, UserInput};
+#[cfg(feature = "ssr")]
+use crate::progress::{Player, WordResult};
+use crate::streak::DailyStreak;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionState {
+    pub id: String,
+    pub language_id: u32,
+    pub mode: PromptMode,
+    pub tile_mode: TileMode,
+    pub word: Word,
+    // Goes up by one with each word dealt
+    pub round: u32,
+    pub attempts: i32,
+    pub is_completed: bool,
+    pub awaiting_pronunciation: bool,
+    // Points and correct words since the session started
+    pub score: i32,
+    pub words_correct: u32,
+    // Earned by the answer just checked
+    pub new_achievements: Vec<Achievement>,
+    // Sent when a session starts and after each finished word
+    pub streak: Option<DailyStreak>,
+}
+
+// How a session is played: what the learner is prompted with and the tiles they answer with
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SessionMode {
+    pub prompt: PromptMode,
+    pub tiles: TileMode,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, sqlx_d1::FromRow)]
+struct SessionRow {
+    id: String,
+    user_id: Option<u32>,
+    profile_id: Option<String>,
+    name: String,
+    language_id: u32,
+    mode: String,
+    tile_mode: String,
+    word_id: u32,
+    round: u32,
+    attempts: i32,
+    is_completed: bool,
+    awaiting_pronunciation: bool,
+    score: i32,
+    words_correct: u32,
+    last_result_id: Option<u32>,
+    utc_offset: i32,
+}
+
+#[cfg(feature = "ssr")]
+impl SessionRow {
+    fn player(&self) -> Player {
+        Player {
+            user_id: self.user_id,
+            profile_id: self.profile_id.clone(),
+        }
+    }
+
+    fn mode(&self) -> PromptMode {
+        PromptMode::from_code(&self.mode).unwrap_or_default()
+    }
+
+    fn tile_mode(&self) -> TileMode {
+        TileMode::from_code(&self.tile_mode).unwrap_or_default()
+    }
+
+    fn game_state(&self, language: Language, word: Word) -> GameState {
+        let mut state = GameState::new(language);
+        state.current_word = word;
+        state.tile_mode = self.tile_mode();
+        state.attempts = self.attempts;
+        state.is_completed = self.is_completed;
+        state.awaiting_pronunciation = self.awaiting_pronunciation;
+        state.score = self.score;
+        state.words_correct = self.words_correct;
+        state
+    }
+
+    fn apply(&mut self, state: &GameState) {
+        self.attempts = state.attempts;
+        self.is_completed = state.is_completed;
+        self.awaiting_pronunciation = state.awaiting_pronunciation;
+        self.score = state.score;
+        self.words_correct = state.words_correct;
+    }
+
+    fn to_state(&self, word: Word) -> SessionState {
+        SessionState {
+            id: self.id.clone(),
+            language_id: self.language_id,
+            mode: self.mode(),
+            tile_mode: self.tile_mode(),
+            word,
+            round: self.round,
+            attempts: self.attempts,
+            is_completed: self.is_completed,
+            awaiting_pronunciation: self.awaiting_pronunciation,
+            score: self.score,
+            words_correct: self.words_correct,
+            new_achievements: vec![],
+            streak: None,
+        }
+    }
+}
+
+// A session can only be played by the learner and account that started it
+#[cfg(feature = "ssr")]
+async fn load_session(
+    conn: &sqlx_d1::D1Connection,
+    session_id: &str,
+    profile_id: Option<String>,
+) -> Result<SessionRow, ServerFnError> {
+    let user_id = crate::auth::get_current_user().await?.map(|user| user.id);
+    sqlx_d1::query_as!(
+        SessionRow,
+        "SELECT id, user_id, profile_id, name, language_id, mode, tile_mode, word_id, round, attempts, is_completed, awaiting_pronunciation, score, words_correct, last_result_id, utc_offset FROM GameSessions WHERE id = ? AND profile_id IS ? AND user_id IS ?",
+        session_id,
+        profile_id,
+        user_id
+    )
+    .fetch_optional(conn)
+    .await
+    .map_err(|e| worker::Error::RustError(e.to_string()))?
+    .ok_or_else(|| ServerFnError::new("Game session not found"))
+}
+
+#[cfg(feature = "ssr")]
+async fn save_session(
+    conn: &sqlx_d1::D1Connection,
+    session: &SessionRow,
+) -> Result<(), ServerFnError> {
+    sqlx_d1::query!(
+        "UPDATE GameSessions SET word_id = ?, round = ?, attempts = ?, is_completed = ?, awaiting_pronunciation = ?, score = ?, words_correct = ?, last_result_id = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+        session.word_id,
+        session.round,
+        session.attempts,
+        session.is_completed,
+        session.awaiting_pronunciation,
+        session.score,
+        session.words_correct,
+        session.last_result_id,
+        session.id
+    )
+    .execute(conn)
+    .await
+    .map_err(|e| worker::Error::RustError(e.to_string()))?;
+
+    Ok(())
+}
+
+// Save a checked answer together with the finished word's result, in one D1 batch. The
+// session is only updated if no other answer has been saved since it was loaded, and the
+// result is only recorded if the update went through, so answers sent at the same time are
+// counted once. Returns whether this answer was the one saved.
+#[cfg(feature = "ssr")]
+async fn save_guess(
+    d1: worker::D1Database,
+    session: &mut SessionRow,
+    loaded_attempts: i32,
+    result: Option<&WordResult>,
+) -> Result<bool, ServerFnError> {
+    let statements = guess_statements(&d1, session, loaded_attempts, result)?;
+    let results = worker::send::SendFuture::new(async move { d1.batch(statements).await }).await?;
+    let changes = |index: usize| {
+        results
+            .get(index)
+            .and_then(|result| result.meta().ok().flatten())
+            .and_then(|meta| meta.changes)
+            .unwrap_or_default()
+    };
+    if changes(0) != 1 {
+        return Ok(false);
+    }
+    if changes(1) == 1 {
+        session.last_result_id = results[1]
+            .meta()?
+            .and_then(|meta| meta.last_row_id)
+            .map(|id| id as u32);
+    }
+    Ok(true)
+}
+
+// The conditional session update, then for a finished word the result and its id on the
+// session. `changes()` is the number of rows the statement before changed.
+#[cfg(feature = "ssr")]
+fn guess_statements(
+    d1: &worker::D1Database,
+    session: &SessionRow,
+    loaded_attempts: i32,
+    result: Option<&WordResult>,
+) -> worker::Result<Vec<worker::D1PreparedStatement>> {
+    use wasm_bindgen::JsValue;
+
+    let mut statements = vec![
+        d1.prepare(
+            "UPDATE GameSessions SET attempts = ?1, is_completed = ?2, awaiting_pronunciation = ?3, score = ?4, words_correct = ?5, last_result_id = NULL, updated_at = CURRENT_TIMESTAMP WHERE id = ?6 AND round = ?7 AND attempts = ?8",
+        )
+        .bind(&[
+            JsValue::from(session.attempts),
+            JsValue::from(session.is_completed),
+            JsValue::from(session.awaiting_pronunciation),
+            JsValue::from(session.score),
+            JsValue::from(session.words_correct),
+            JsValue::from(session.id.as_str()),
+            JsValue::from(session.round),
+            JsValue::from(loaded_attempts),
+        ])?,
+    ];
+    // Guests have nowhere to keep their history
+    if let Some(result) = result.filter(|_| !session.player().is_guest()) {
+        let local_offset = crate::streak::date_modifier(result.utc_offset);
+        statements.push(
+            d1.prepare(
+                "INSERT INTO GameResults (user_id, profile_id, word_id, language_id, attempts, correct, score, mode, local_date, session_id) SELECT ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, date('now', ?9), ?10 WHERE changes() = 1",
+            )
+            .bind(&[
+                JsValue::from(session.user_id),
+                JsValue::from(session.profile_id.clone()),
+                JsValue::from(result.word_id),
+                JsValue::from(result.language_id),
+                JsValue::from(result.attempts),
+                JsValue::from(result.correct),
+                JsValue::from(result.score),
+                JsValue::from(result.mode.code()),
+                JsValue::from(local_offset),
+                JsValue::from(session.id.as_str()),
+            ])?,
+        );
+        statements.push(
+            d1.prepare(
+                "UPDATE GameSessions SET last_result_id = last_insert_rowid() WHERE id = ?1 AND changes() = 1",
+            )
+            .bind(&[JsValue::from(session.id.as_str())])?,
+        );
+    }
+    Ok(statements)
+}
+
+// Save the pronunciation bonus to the session and to the finished word's result in one D1
+// batch, in the same way as `save_guess`: only the call that clears
+// `awaiting_pronunciation` adds the bonus. Returns whether this call was the one saved.
+#[cfg(feature = "ssr")]
+async fn save_pronunciation(
+    d1: worker::D1Database,
+    session: &SessionRow,
+    bonus: i32,
+) -> Result<bool, ServerFnError> {
+    let statements = pronunciation_statements(&d1, session, bonus)?;
+    let results = worker::send::SendFuture::new(async move { d1.batch(statements).await }).await?;
+    let saved = results
+        .first()
+        .and_then(|result| result.meta().ok().flatten())
+        .and_then(|meta| meta.changes)
+        == Some(1);
+    Ok(saved)
+}
+
+#[cfg(feature = "ssr")]
+fn pronunciation_statements(
+    d1: &worker::D1Database,
+    session: &SessionRow,
+    bonus: i32,
+) -> worker::Result<Vec<worker::D1PreparedStatement>> {
+    use wasm_bindgen::JsValue;
+
+    let mut statements = vec![
+        d1.prepare(
+            "UPDATE GameSessions SET awaiting_pronunciation = 0, score = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2 AND awaiting_pronunciation = 1",
+        )
+        .bind(&[JsValue::from(session.score), JsValue::from(session.id.as_str())])?,
+    ];
+    if bonus > 0
+        && let Some(result_id) = session.last_result_id
+    {
+        statements.push(
+            d1.prepare("UPDATE GameResults SET score = score + ?1 WHERE id = ?2 AND changes() = 1")
+                .bind(&[JsValue::from(bonus), JsValue::from(result_id)])?,
+        );
+    }
+    Ok(statements)
+}
+
+#[cfg(feature = "ssr")]
+async fn load_language(
+    conn: &sqlx_d1::D1Connection,
+    language_id: u32,
+) -> Result<Language, ServerFnError> {
+    sqlx_d1::query_as!(
+        Language,
+        "SELECT id, name, name_other, code, strip_diacritics, direction, speech_locale FROM Languages WHERE id = ?",
+        language_id
+    )
+    .fetch_optional(conn)
+    .await
+    .map_err(|e| worker::Error::RustError(e.to_string()))?
+    .ok_or_else(|| ServerFnError::new(format!("Language {language_id} not found")))
+}
+
+// The word as it is dealt to the learner, after the same clean-up as the scheduler applies
+#[cfg(feature = "ssr")]
+async fn load_word(
+    conn: &sqlx_d1::D1Connection,
+    language: &Language,
+    word_id: u32,
+) -> Result<Word, ServerFnError> {
+    let mut word = sqlx_d1::query_as!(
+        Word,
+        "SELECT id, word, language_id, audio_url, image_url, emoji, category FROM Words WHERE id = ?",
+        word_id
+    )
+    .fetch_optional(conn)
+    .await
+    .map_err(|e| worker::Error::RustError(e.to_string()))?
+    .ok_or_else(|| ServerFnError::new(format!("Word {word_id} not found")))?;
+    word.word = word.post_process(language).map_err(ServerFnError::new)?;
+
+    Ok(word)
+}
+
+// Start playing a language in a prompt mode and deal the first word. The tile mode is kept
+// for the whole session, so answers are always scored the same way.
+#[server]
+pub async fn start_game_session(
+    language_id: u32,
+    profile_id: Option<String>,
+    name: Option<String>,
+    mode: SessionMode,
+    previous_word_id: Option<u32>,
+    weak_letters: bool,
+    utc_offset: i32,
+) -> Result<SessionState, ServerFnError> {
+    use axum::Extension;
+    use std::sync::Arc;
+    use worker::Env;
+
+    let user = crate::auth::get_current_user().await?;
+    let name = crate::leaderboard::player_name(name.as_deref(), user.as_ref());
+    let Extension::<Arc<Env>>(env) = leptos_axum::extract().await?;
+    let d1 = env.d1("alphabet_game_stg")?;
+    let conn = sqlx_d1::D1Connection::new(d1);
+
+    let language = load_language(&conn, language_id).await?;
+    let word = crate::database::get_next_word_for_language(
+        language,
+        profile_id.clone(),
+        previous_word_id,
+        weak_letters,
+    )
+    .await?;
+    let session = SessionRow {
+        id: crate::auth::random_bytes::<16>()?
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect(),
+        user_id: user.map(|user| user.id),
+        profile_id,
+        name,
+        language_id,
+        mode: mode.prompt.code().to_string(),
+        tile_mode: mode.tiles.code().to_string(),
+        word_id: word.id,
+        round: 1,
+        attempts: 0,
+        is_completed: false,
+        awaiting_pronunciation: false,
+        score: 0,
+        words_correct: 0,
+        last_result_id: None,
+        utc_offset,
+    };
+    sqlx_d1::query!(
+        "INSERT INTO GameSessions (id, user_id, profile_id, name, language_id, mode, tile_mode, word_id, utc_offset) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        session.id,
+        session.user_id,
+        session.profile_id,
+        session.name,
+        session.language_id,
+        session.mode,
+        session.tile_mode,
+        session.word_id,
+        session.utc_offset
+    )
+    .execute(&conn)
+    .await
+    .map_err(|e| worker::Error::RustError(e.to_string()))?;
+
+    let mut reply = session.to_state(word);
+    reply.streak =
+        Some(crate::streak::daily_streak(&conn, &session.player(), session.utc_offset).await?);
+    Ok(reply)
+}
+
+// Check an answer to the current word. Finished words are recorded for the learner and their
+// points posted to the leaderboard, then any achievements the answer earned are awarded.
+#[server(input = Json)]
+pub async fn submit_guess(
+    session_id: String,
+    profile_id: Option<String>,
+    answer: UserInput,
+    say_the_word: bool,
+) -> Result<SessionState, ServerFnError> {
+    use axum::Extension;
+    use std::sync::Arc;
+    use worker::Env;
+
+    use crate::{leaderboard, mastery, progress};
+
+    let Extension::<Arc<Env>>(env) = leptos_axum::extract().await?;
+    let d1 = env.d1("alphabet_game_stg")?;
+    let conn = sqlx_d1::D1Connection::new(d1);
+
+    let mut session = load_session(&conn, &session_id, profile_id).await?;
+    let language = load_language(&conn, session.language_id).await?;
+    let word = load_word(&conn, &language, session.word_id).await?;
+    if session.is_completed {
+        return Ok(session.to_state(word));
+    }
+
+    let mut state = session.game_state(language.clone(), word.clone());
+    // Compose the answer again from its tiles in the session's tile mode rather than trusting
+    // the text and mode sent with them
+    state.user_input = UserInput::with_mode(state.tile_mode);
+    for part in answer.parts.iter().take(word.len(state.tile_mode)) {
+        state.user_input.add_letter(part);
+    }
+    let player = session.player();
+    let mut letter_results = vec![];
+    if !state.user_input.is_empty() {
+        let letters = crate::database::get_letters_for_language(language.clone()).await?;
+        state.set_language_letters(letters);
+        let feedback = mastery::letter_feedback(&state.user_input.word, &word.word, &language.code);
+        letter_results =
+            mastery::feedback_letter_ids(&feedback, &state.letter_details, &language.code);
+    }
+
+    let score_before = state.score;
+    let correct = state.check_spelling();
+    state.awaiting_pronunciation = correct && say_the_word;
+    let result = state.is_completed.then(|| WordResult {
+        word_id: word.id,
+        language_id: language.id,
+        attempts: state.attempts,
+        correct,
+        score: state.score - score_before,
+        mode: session.mode(),
+        utc_offset: session.utc_offset,
+    });
+    let loaded_attempts = session.attempts;
+    session.apply(&state);
+    if !save_guess(
+        env.d1("alphabet_game_stg")?,
+        &mut session,
+        loaded_attempts,
+        result.as_ref(),
+    )
+    .await?
+    {
+        return Err(ServerFnError::new("This answer has already been checked"));
+    }
+    progress::record_letter_results(&conn, &player, language.id, &letter_results).await?;
+    if correct {
+        leaderboard::submit_score(
+            &conn,
+            &player,
+            &session.id,
+            &session.name,
+            session.language_id,
+            session.mode(),
+        )
+        .await?;
+    }
+
+    let mut reply = session.to_state(word);
+    reply.new_achievements = crate::achievements::award(&conn, &player, &language).await?;
+    if session.is_completed {
+        reply.streak = Some(crate::streak::daily_streak(&conn, &player, session.utc_offset).await?);
+    }
+    Ok(reply)
+}
+
+// Deal the next word once the current one is finished
+#[server]
+pub async fn next_session_word(
+    session_id: String,
+    profile_id: Option<String>,
+    weak_letters: bool,
+) -> Result<SessionState, ServerFnError> {
+    use axum::Extension;
+    use std::sync::Arc;
+    use worker::Env;
+
+    let Extension::<Arc<Env>>(env) = leptos_axum::extract().await?;
+    let d1 = env.d1("alphabet_game_stg")?;
+    let conn = sqlx_d1::D1Connection::new(d1);
+
+    let mut session = load_session(&conn, &session_id, profile_id.clone()).await?;
+    if !session.is_completed || session.awaiting_pronunciation {
+        return Err(ServerFnError::new("The current word is not finished"));
+    }
+    let language = load_language(&conn, session.language_id).await?;
+    let word = crate::database::get_next_word_for_language(
+        language,
+        profile_id,
+        Some(session.word_id),
+        weak_letters,
+    )
+    .await?;
+    session.word_id = word.id;
+    session.round += 1;
+    session.attempts = 0;
+    session.is_completed = false;
+    session.awaiting_pronunciation = false;
+    session.last_result_id = None;
+    save_session(&conn, &session).await?;
+
+    Ok(session.to_state(word))
+}
+
+// Whether the learner said the word they just spelled. Recognition runs in the browser, so
+// this is taken on trust, but the bonus is only given once for a correctly spelled word.
+#[server]
+pub async fn record_session_pronunciation(
+    session_id: String,
+    profile_id: Option<String>,
+    is_correct: bool,
+) -> Result<SessionState, ServerFnError> {
+    use axum::Extension;
+    use std::sync::Arc;
+    use worker::Env;
+
+    use crate::leaderboard;
+
+    let Extension::<Arc<Env>>(env) = leptos_axum::extract().await?;
+    let d1 = env.d1("alphabet_game_stg")?;
+    let conn = sqlx_d1::D1Connection::new(d1);
+
+    let mut session = load_session(&conn, &session_id, profile_id).await?;
+    let language = load_language(&conn, session.language_id).await?;
+    let word = load_word(&conn, &language, session.word_id).await?;
+    if !session.awaiting_pronunciation {
+        return Ok(session.to_state(word));
+    }
+
+    let mut state = session.game_state(language, word.clone());
+    state.record_pronunciation(is_correct);
+    let bonus = state.score - session.score;
+    session.apply(&state);
+    if !save_pronunciation(env.d1("alphabet_game_stg")?, &session, bonus).await? {
+        return Err(ServerFnError::new(
+            "The pronunciation has already been recorded",
+        ));
+    }
+    if bonus > 0 {
+        leaderboard::submit_score(
+            &conn,
+            &session.player(),
+            &session.id,
+            &session.name,
+            session.language_id,
+            session.mode(),
+        )
+        .await?;
+    }
+
+    Ok(session.to_state(word))
+}
@@ -1,19 +1,193 @@
+use std::fmt;
+
 use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::{JsCast, prelude::Closure};
+use web_sys::{
+    HtmlAudioElement, SpeechSynthesis, SpeechSynthesisUtterance, SpeechSynthesisVoice, js_sys,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SpeechSettings {
+    pub rate: f32,
+    pub pitch: f32,
+    pub volume: f32,
+}
+
+impl Default for SpeechSettings {
+    fn default() -> Self {
+        Self {
+            rate: 1.0,
+            pitch: 1.0,
+            volume: 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpeechError {
+    Unavailable,
+    NoVoice(String),
+}
 
-pub fn speak(text: &str, lang: &str) {
+impl fmt::Display for SpeechError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpeechError::Unavailable => write!(f, "Speech is not available in this browser"),
+            SpeechError::NoVoice(locale) => write!(f, "No voice installed for {locale}"),
+        }
+    }
+}
+
+fn synth() -> Result<SpeechSynthesis, SpeechError> {
+    window()
+        .speech_synthesis()
+        .map_err(|_| SpeechError::Unavailable)
+}
+
+fn primary_language(locale: &str) -> String {
+    locale
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+fn same_locale(a: &str, b: &str) -> bool {
+    a.replace('_', "-")
+        .eq_ignore_ascii_case(&b.replace('_', "-"))
+}
+
+fn voice_rank(voice: &&SpeechSynthesisVoice) -> (bool, bool) {
+    (voice.default(), voice.local_service())
+}
+
+pub fn voices() -> Vec<SpeechSynthesisVoice> {
+    match synth() {
+        Ok(synth) => synth
+            .get_voices()
+            .iter()
+            .filter_map(|voice| voice.dyn_into::<SpeechSynthesisVoice>().ok())
+            .collect(),
+        Err(_) => vec![],
+    }
+}
+
+// Pick the best voice for a BCP-47 locale: an exact match first, then any voice for the
+// same language, preferring the browser default and voices that work offline
+pub fn find_voice(voices: &[SpeechSynthesisVoice], locale: &str) -> Option<SpeechSynthesisVoice> {
+    let exact = voices
+        .iter()
+        .filter(|voice| same_locale(&voice.lang(), locale))
+        .max_by_key(voice_rank);
+    let language = primary_language(locale);
+    exact
+        .or_else(|| {
+            voices
+                .iter()
+                .filter(|voice| primary_language(&voice.lang()) == language)
+                .max_by_key(voice_rank)
+        })
+        .cloned()
+}
+
+// Check whether the browser can speak a locale. Voices load asynchronously, so an empty
+// list is treated as unknown rather than missing.
+pub fn voice_status(locale: &str) -> Result<(), SpeechError> {
+    synth()?;
+    let voices = voices();
+    if !voices.is_empty() && find_voice(&voices, locale).is_none() {
+        return Err(SpeechError::NoVoice(locale.to_string()));
+    }
+    Ok(())
+}
+
+// Run a callback when the browser finishes loading its voice list
+pub fn on_voices_changed(callback: impl Fn() + 'static) {
+    if let Ok(synth) = synth() {
+        let closure = Closure::<dyn Fn()>::new(callback);
+        synth.set_onvoiceschanged(Some(closure.as_ref().unchecked_ref()));
+        closure.forget();
+    }
+}
+
+// Speak text in a locale, cancelling anything still being spoken
+pub fn speak(text: &str, locale: &str, settings: &SpeechSettings) -> Result<(), SpeechError> {
     if text.is_empty() {
-        return;
+        return Ok(());
     }
-    if let Ok(synth) = window().speech_synthesis() {
-        match web_sys::SpeechSynthesisUtterance::new_with_text(text) {
-            Ok(utterance) => {
-                utterance.set_lang(lang);
-                synth.speak(&utterance);
-            }
-            Err(e) => {
-                let error_message = format!("Error creating utterance: {:?}", e);
-                leptos::logging::error!("{}", error_message);
+    let synth = synth()?;
+    let voices = voices();
+    let voice = find_voice(&voices, locale);
+    match web_sys::SpeechSynthesisUtterance::new_with_text(text) {
+        Ok(utterance) => {
+            configure(&utterance, locale, voice.as_ref(), settings);
+            synth.cancel();
+            synth.speak(&utterance);
+        }
+        Err(e) => {
+            let error_message = format!("Error creating utterance: {:?}", e);
+            leptos::logging::error!("{}", error_message);
+        }
+    }
+    match voice {
+        None if !voices.is_empty() => Err(SpeechError::NoVoice(locale.to_string())),
+        _ => Ok(()),
+    }
+}
+
+// Play a recorded pronunciation when there is one, falling back to speech synthesis
+// if the recording is missing or the browser refuses to play it
+pub fn pronounce(
+    text: &str,
+    audio_url: Option<&str>,
+    locale: &str,
+    settings: &SpeechSettings,
+) -> Result<(), SpeechError> {
+    let Some(audio_url) = audio_url.filter(|url| !url.is_empty()) else {
+        return speak(text, locale, settings);
+    };
+    let (text, locale, settings) = (text.to_string(), locale.to_string(), *settings);
+    let fallback = move || {
+        if let Err(e) = speak(&text, &locale, &settings) {
+            leptos::logging::log!("Speech fallback failed: {e}");
+        }
+    };
+    match play_audio(audio_url, settings.volume) {
+        Ok(playing) => {
+            if let Ok(synth) = synth() {
+                synth.cancel();
             }
+            let on_error = Closure::once(move |e: wasm_bindgen::JsValue| {
+                leptos::logging::log!("Error playing audio: {:?}", e);
+                fallback();
+            });
+            let _ = playing.catch(&on_error);
+            on_error.forget();
+        }
+        Err(e) => {
+            leptos::logging::log!("Error creating audio: {:?}", e);
+            fallback();
         }
     }
+    Ok(())
+}
+
+fn play_audio(url: &str, volume: f32) -> Result<js_sys::Promise, wasm_bindgen::JsValue> {
+    let audio = HtmlAudioElement::new_with_src(url)?;
+    audio.set_volume(volume.into());
+    audio.play()
+}
+
+fn configure(
+    utterance: &SpeechSynthesisUtterance,
+    locale: &str,
+    voice: Option<&SpeechSynthesisVoice>,
+    settings: &SpeechSettings,
+) {
+    utterance.set_lang(locale);
+    utterance.set_voice(voice);
+    utterance.set_rate(settings.rate);
+    utterance.set_pitch(settings.pitch);
+    utterance.set_volume(settings.volume);
 }
@@ -0,0 +1,435 @@
+// Daily practice: runs of days with at least one word played, counted in the learner's own
+// time zone, a goal of correct words per day, and evening reminders for learners who ask.
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ssr")]
+use std::sync::Arc;
+
+#[cfg(feature = "ssr")]
+use crate::auth::{HttpEmailSender, NoEmailSender, SendResult};
+#[cfg(feature = "ssr")]
+use crate::progress::Player;
+
+// Choices offered for the daily goal; 0 means no goal
+pub const DAILY_GOALS: [u32; 4] = [0, 5, 10, 20];
+#[cfg(feature = "ssr")]
+const DAY: i64 = 24 * 60 * 60;
+// Local hour at which learners who have not met their goal are reminded
+#[cfg(feature = "ssr")]
+const REMINDER_HOUR: i64 = 18;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct DailyStreak {
+    // Days in a row up to today, or up to yesterday while today is still to play
+    pub days: u32,
+    // Words spelled correctly today
+    pub words_today: u32,
+    // Whether any word was finished today, right or wrong, as counted for `days`
+    pub played_today: bool,
+}
+
+impl DailyStreak {
+    pub fn goal_met(&self, daily_goal: u32) -> bool {
+        daily_goal > 0 && self.words_today >= daily_goal
+    }
+}
+
+// Minutes east of UTC in the browser's time zone
+pub fn utc_offset() -> i32 {
+    -(web_sys::js_sys::Date::new_0().get_timezone_offset() as i32)
+}
+
+// Most days in a row in a list of day numbers, sorted and without repeats
+pub fn best_day_run(days: &[i64]) -> u32 {
+    let mut best = 0;
+    let mut run = 0;
+    let mut previous = None;
+    for &day in days {
+        run = if previous == Some(day - 1) {
+            run + 1
+        } else {
+            1
+        };
+        best = best.max(run);
+        previous = Some(day);
+    }
+    best
+}
+
+// Days in a row ending today, or yesterday so a streak is not lost before today's practice
+pub fn current_day_run(days: &[i64], today: i64) -> u32 {
+    let mut expected = match days.last() {
+        Some(&last) if last == today || last == today - 1 => last,
+        _ => return 0,
+    };
+    let mut run = 0;
+    for &day in days.iter().rev() {
+        if day != expected {
+            break;
+        }
+        run += 1;
+        expected -= 1;
+    }
+    run
+}
+
+// SQLite date modifier that shifts UTC to the learner's local time
+#[cfg(feature = "ssr")]
+pub fn date_modifier(utc_offset: i32) -> String {
+    format!("{utc_offset:+} minutes")
+}
+
+// Today's day number in the learner's time zone, counted like the stored local dates
+#[cfg(feature = "ssr")]
+fn local_today(utc_offset: i32) -> i64 {
+    let now = (worker::Date::now().as_millis() / 1000) as i64;
+    (now + i64::from(utc_offset) * 60).div_euclid(DAY)
+}
+
+// Local days on which the learner finished a word, as day numbers in order
+#[cfg(feature = "ssr")]
+pub async fn practice_days(
+    conn: &sqlx_d1::D1Connection,
+    player: &Player,
+) -> Result<Vec<i64>, ServerFnError> {
+    let days = match (&player.profile_id, player.user_id) {
+        (Some(profile_id), _) => sqlx_d1::query!(
+            "SELECT DISTINCT CAST(strftime('%s', local_date) / 86400 AS INTEGER) AS day FROM GameResults WHERE profile_id = ? ORDER BY day",
+            profile_id
+        )
+        .fetch_all(conn)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?
+        .into_iter()
+        .filter_map(|row| row.day)
+        .collect(),
+        (None, Some(user_id)) => sqlx_d1::query!(
+            "SELECT DISTINCT CAST(strftime('%s', local_date) / 86400 AS INTEGER) AS day FROM GameResults WHERE user_id = ? ORDER BY day",
+            user_id
+        )
+        .fetch_all(conn)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?
+        .into_iter()
+        .filter_map(|row| row.day)
+        .collect(),
+        (None, None) => vec![],
+    };
+
+    Ok(days)
+}
+
+#[cfg(feature = "ssr")]
+pub async fn daily_streak(
+    conn: &sqlx_d1::D1Connection,
+    player: &Player,
+    utc_offset: i32,
+) -> Result<DailyStreak, ServerFnError> {
+    let days = practice_days(conn, player).await?;
+    let local_offset = date_modifier(utc_offset);
+    let words_today = match (&player.profile_id, player.user_id) {
+        (Some(profile_id), _) => sqlx_d1::query!(
+            "SELECT COUNT(*) AS words FROM GameResults WHERE profile_id = ? AND correct AND local_date = date('now', ?)",
+            profile_id,
+            local_offset
+        )
+        .fetch_one(conn)
+        .await
+        .map(|row| row.words)
+        .map_err(|e| worker::Error::RustError(e.to_string()))?,
+        (None, Some(user_id)) => sqlx_d1::query!(
+            "SELECT COUNT(*) AS words FROM GameResults WHERE user_id = ? AND correct AND local_date = date('now', ?)",
+            user_id,
+            local_offset
+        )
+        .fetch_one(conn)
+        .await
+        .map(|row| row.words)
+        .map_err(|e| worker::Error::RustError(e.to_string()))?,
+        (None, None) => 0,
+    };
+
+    let today = local_today(utc_offset);
+    Ok(DailyStreak {
+        days: current_day_run(&days, today),
+        words_today: words_today as u32,
+        played_today: days.last() == Some(&today),
+    })
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reminder {
+    pub email: String,
+    pub name: String,
+    pub daily_goal: u32,
+    pub streak: DailyStreak,
+}
+
+#[cfg(feature = "ssr")]
+impl Reminder {
+    // Learners with a goal are reminded until they meet it, and learners without one until
+    // they have practised today at all
+    pub fn is_due(&self) -> bool {
+        match self.daily_goal {
+            0 => !self.streak.played_today,
+            goal => !self.streak.goal_met(goal),
+        }
+    }
+
+    pub fn subject(&self) -> String {
+        format!("Time to practise, {}!", self.name)
+    }
+
+    pub fn text(&self) -> String {
+        let goal = match self.daily_goal {
+            0 => "You have not practised yet today.".to_string(),
+            goal => format!(
+                "{} of today's {goal} words spelled so far.",
+                self.streak.words_today
+            ),
+        };
+        match self.streak.days {
+            0 => format!("{goal} A few words today will start a new streak."),
+            days => format!("{goal} Keep your {days}-day streak going!"),
+        }
+    }
+}
+
+// Delivers practice reminders; swapped for `LogNotifier` in local development and tests
+#[cfg(feature = "ssr")]
+pub trait ReminderNotifier: Send + Sync {
+    fn send_reminder<'a>(&'a self, reminder: &'a Reminder) -> SendResult<'a>;
+}
+
+// Writes the reminder to the Worker log instead of sending mail
+#[cfg(feature = "ssr")]
+pub struct LogNotifier;
+
+#[cfg(feature = "ssr")]
+impl ReminderNotifier for LogNotifier {
+    fn send_reminder<'a>(&'a self, reminder: &'a Reminder) -> SendResult<'a> {
+        Box::pin(async move {
+            leptos::logging::log!("Reminder for {}: {}", reminder.email, reminder.text());
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl ReminderNotifier for HttpEmailSender {
+    fn send_reminder<'a>(&'a self, reminder: &'a Reminder) -> SendResult<'a> {
+        Box::pin(async move {
+            self.send(&reminder.email, &reminder.subject(), &reminder.text())
+                .await
+        })
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl ReminderNotifier for NoEmailSender {
+    fn send_reminder<'a>(&'a self, _reminder: &'a Reminder) -> SendResult<'a> {
+        Box::pin(async { Err("Email is not configured".to_string()) })
+    }
+}
+
+// Reminders go out by email when the email API is configured, and to the log only when
+// EMAIL_LOG_ONLY is set
+#[cfg(feature = "ssr")]
+pub fn reminder_notifier(env: &worker::Env) -> Arc<dyn ReminderNotifier> {
+    match HttpEmailSender::from_env(env) {
+        Some(sender) => Arc::new(sender),
+        None if crate::auth::log_email_only(env) => Arc::new(LogNotifier),
+        None => Arc::new(NoEmailSender),
+    }
+}
+
+// Remind learners for whom it is now the reminder hour and who have not met their goal today.
+// Run every hour by the Worker's cron trigger, so each time zone is reached in turn.
+#[cfg(feature = "ssr")]
+pub async fn send_due_reminders(
+    conn: &sqlx_d1::D1Connection,
+    notifier: &dyn ReminderNotifier,
+) -> Result<u32, ServerFnError> {
+    let due = sqlx_d1::query!(
+        "SELECT r.owner, r.user_id, r.profile_id, r.name, r.daily_goal, r.utc_offset, u.email FROM PracticeReminders r JOIN Users u ON u.id = r.user_id WHERE CAST(strftime('%H', 'now', printf('%+d minutes', r.utc_offset)) AS INTEGER) = ? AND (r.last_sent IS NULL OR r.last_sent != date('now', printf('%+d minutes', r.utc_offset)))",
+        REMINDER_HOUR
+    )
+    .fetch_all(conn)
+    .await
+    .map_err(|e| worker::Error::RustError(e.to_string()))?;
+
+    let mut sent = 0;
+    for row in due {
+        let utc_offset = row.utc_offset as i32;
+        let player = Player {
+            user_id: Some(row.user_id as u32),
+            profile_id: row.profile_id,
+        };
+        let reminder = Reminder {
+            email: row.email,
+            name: row.name,
+            daily_goal: row.daily_goal as u32,
+            streak: daily_streak(conn, &player, utc_offset).await?,
+        };
+        if !reminder.is_due() {
+            continue;
+        }
+        if let Err(e) = notifier.send_reminder(&reminder).await {
+            leptos::logging::error!("Error sending reminder: {e}");
+            continue;
+        }
+        let local_offset = date_modifier(utc_offset);
+        sqlx_d1::query!(
+            "UPDATE PracticeReminders SET last_sent = date('now', ?) WHERE owner = ?",
+            local_offset,
+            row.owner
+        )
+        .execute(conn)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?;
+        sent += 1;
+    }
+
+    Ok(sent)
+}
+
+// Keep the server's copy of the learner's daily goal and whether they want reminders.
+// Reminders are sent by email, so guests have nothing to save.
+#[server]
+pub async fn save_practice_reminder(
+    profile_id: Option<String>,
+    name: Option<String>,
+    daily_goal: u32,
+    enabled: bool,
+    utc_offset: i32,
+) -> Result<(), ServerFnError> {
+    use axum::Extension;
+    use worker::Env;
+
+    let Some(user) = crate::auth::get_current_user().await? else {
+        return Ok(());
+    };
+    let player = Player {
+        user_id: Some(user.id),
+        profile_id,
+    };
+    let Some(owner) = player.owner() else {
+        return Ok(());
+    };
+    let name = crate::leaderboard::player_name(name.as_deref(), Some(&user));
+    let Extension::<Arc<Env>>(env) = leptos_axum::extract().await?;
+    let d1 = env.d1("alphabet_game_stg")?;
+    let conn = sqlx_d1::D1Connection::new(d1);
+
+    if enabled {
+        sqlx_d1::query!(
+            "INSERT INTO PracticeReminders (owner, user_id, profile_id, name, daily_goal, utc_offset) VALUES (?, ?, ?, ?, ?, ?) ON CONFLICT(owner) DO UPDATE SET name = excluded.name, daily_goal = excluded.daily_goal, utc_offset = excluded.utc_offset, updated_at = CURRENT_TIMESTAMP",
+            owner,
+            user.id,
+            player.profile_id,
+            name,
+            daily_goal,
+            utc_offset
+        )
+        .execute(&conn)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?;
+    } else {
+        sqlx_d1::query!(
+            "DELETE FROM PracticeReminders WHERE owner = ? AND user_id = ?",
+            owner,
+            user.id
+        )
+        .execute(&conn)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn best_day_run_counts_the_longest_run() {
+        assert_eq!(best_day_run(&[]), 0);
+        assert_eq!(best_day_run(&[10]), 1);
+        assert_eq!(best_day_run(&[1, 2, 3, 5, 6]), 3);
+        assert_eq!(best_day_run(&[1, 3, 4, 5, 6, 9]), 4);
+    }
+
+    #[test]
+    fn current_day_run_ends_today_or_yesterday() {
+        assert_eq!(current_day_run(&[], 10), 0);
+        assert_eq!(current_day_run(&[8, 9, 10], 10), 3);
+        // Today is still to play, so yesterday's run is kept
+        assert_eq!(current_day_run(&[8, 9], 10), 2);
+        assert_eq!(current_day_run(&[7, 8], 10), 0);
+        // A gap ends the run
+        assert_eq!(current_day_run(&[5, 6, 8, 9, 10], 10), 3);
+    }
+
+    #[cfg(feature = "ssr")]
+    fn reminder(daily_goal: u32, streak: DailyStreak) -> Reminder {
+        Reminder {
+            email: "learner@example.com".to_string(),
+            name: "Sam".to_string(),
+            daily_goal,
+            streak,
+        }
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn reminder_text_mentions_the_goal_and_streak() {
+        let streak = DailyStreak {
+            days: 3,
+            words_today: 2,
+            played_today: true,
+        };
+        assert_eq!(
+            reminder(5, streak).text(),
+            "2 of today's 5 words spelled so far. Keep your 3-day streak going!"
+        );
+        assert_eq!(
+            reminder(0, DailyStreak::default()).text(),
+            "You have not practised yet today. A few words today will start a new streak."
+        );
+        assert_eq!(reminder(0, streak).subject(), "Time to practise, Sam!");
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn reminders_stop_once_the_goal_is_met() {
+        let streak = |words_today, played_today| DailyStreak {
+            days: 1,
+            words_today,
+            played_today,
+        };
+        assert!(reminder(5, streak(4, true)).is_due());
+        assert!(!reminder(5, streak(5, true)).is_due());
+        // Without a goal any finished word counts, even one spelled wrong
+        assert!(reminder(0, streak(0, false)).is_due());
+        assert!(!reminder(0, streak(0, true)).is_due());
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn reminders_are_logged_or_refused_without_email() {
+        let reminder = reminder(5, DailyStreak::default());
+        let send = |notifier: &dyn ReminderNotifier| {
+            let mut future = notifier.send_reminder(&reminder);
+            let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+            match future.as_mut().poll(&mut context) {
+                std::task::Poll::Ready(result) => result,
+                std::task::Poll::Pending => panic!("notifier future did not finish"),
+            }
+        };
+        assert_eq!(send(&LogNotifier), Ok(()));
+        assert!(send(&NoEmailSender).is_err());
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::database::{self, Language, Letter, TileMode, Word, WordError};
+use crate::normalize;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Issue {
+    NoLetters,
+    NoWords,
+    InvalidWord {
+        word_id: u32,
+        word: String,
+        error: WordError,
+    },
+    // Letters the grid would show that are not in the language's alphabet
+    UnknownLetters {
+        word_id: u32,
+        word: String,
+        letters: Vec<String>,
+    },
+    Duplicate {
+        word_id: u32,
+        word: String,
+        duplicate_of: u32,
+    },
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::NoLetters => write!(f, "The language has no letters"),
+            Issue::NoWords => write!(f, "The language has no words"),
+            Issue::InvalidWord { error, .. } => write!(f, "{error}"),
+            Issue::UnknownLetters { word, letters, .. } => {
+                write!(f, "Unknown letters in \"{word}\": {}", letters.join(", "))
+            }
+            Issue::Duplicate {
+                word, duplicate_of, ..
+            } => write!(f, "\"{word}\" is a duplicate of word {duplicate_of}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LanguageReport {
+    pub language: String,
+    pub letter_count: usize,
+    pub word_count: usize,
+    pub issues: Vec<Issue>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ValidationReport {
+    pub ok: bool,
+    pub languages: Vec<LanguageReport>,
+}
+
+impl ValidationReport {
+    pub fn new(languages: Vec<LanguageReport>) -> Self {
+        Self {
+            ok: languages.iter().all(|l| l.issues.is_empty()),
+            languages,
+        }
+    }
+}
+
+// Check every word the way the game will deal it: post-processed, then split into tiles.
+// Letters and words are compared case-insensitively since answers are.
+pub fn validate_language(
+    language: &Language,
+    letters: &[Letter],
+    words: &[Word],
+) -> LanguageReport {
+    let mut issues = Vec::new();
+    if letters.is_empty() {
+        issues.push(Issue::NoLetters);
+    }
+    if words.is_empty() {
+        issues.push(Issue::NoWords);
+    }
+
+    let alphabet: HashSet<String> = letters
+        .iter()
+        .map(|l| normalize::normalize_answer(&l.letter, &language.code))
+        .collect();
+    let mut seen: HashMap<String, u32> = HashMap::new();
+    for word in words {
+        let processed = match word.post_process(language) {
+            Ok(processed) => processed,
+            Err(error) => {
+                issues.push(Issue::InvalidWord {
+                    word_id: word.id,
+                    word: word.word.clone(),
+                    error,
+                });
+                continue;
+            }
+        };
+
+        let key = normalize::normalize_answer(&processed, &language.code);
+        if let Some(&duplicate_of) = seen.get(&key) {
+            issues.push(Issue::Duplicate {
+                word_id: word.id,
+                word: processed.clone(),
+                duplicate_of,
+            });
+        } else {
+            seen.insert(key, word.id);
+        }
+
+        if !letters.is_empty() {
+            let mut unknown: Vec<String> = Vec::new();
+            for tile in database::decompose(processed.clone(), TileMode::Letters) {
+                let known = database::is_separator(&tile)
+                    || alphabet.contains(&normalize::normalize_answer(&tile, &language.code));
+                if !known && !unknown.contains(&tile) {
+                    unknown.push(tile);
+                }
+            }
+            if !unknown.is_empty() {
+                issues.push(Issue::UnknownLetters {
+                    word_id: word.id,
+                    word: processed,
+                    letters: unknown,
+                });
+            }
+        }
+    }
+
+    LanguageReport {
+        language: language.code.clone(),
+        letter_count: letters.len(),
+        word_count: words.len(),
+        issues,
+    }
+}
+
+// Issues a single word would add to its language, used before saving it
+pub fn check_word(
+    language: &Language,
+    letters: &[Letter],
+    existing: &[Word],
+    word: &Word,
+) -> Vec<Issue> {
+    let mut words: Vec<Word> = existing
+        .iter()
+        .filter(|w| w.id != word.id)
+        .cloned()
+        .collect();
+    words.push(word.clone());
+    validate_language(language, letters, &words)
+        .issues
+        .into_iter()
+        .filter(|issue| match issue {
+            Issue::InvalidWord { word_id, .. }
+            | Issue::UnknownLetters { word_id, .. }
+            | Issue::Duplicate { word_id, .. } => *word_id == word.id,
+            Issue::NoLetters | Issue::NoWords => false,
+        })
+        .collect()
+}